@@ -0,0 +1,62 @@
+use crate::{die, info};
+use recover::ext4::{file_sys::Error, FileSystem, Superblock};
+use std::fs::File;
+use std::path::PathBuf;
+
+pub(crate) struct Backups
+{
+  pub(crate) path: PathBuf,
+  pub(crate) offset: u64,
+}
+
+impl Backups
+{
+  pub(crate) fn run(&self)
+  {
+    if let Err(err) = self.list() {
+      die!("{}", err);
+    }
+  }
+
+  fn list(&self) -> Result<(), Error>
+  {
+    let mut img = File::open(self.path.as_path())?;
+    let backups = FileSystem::find_backup_superblocks(&mut img, self.offset)?;
+    if backups.is_empty() {
+      info!("No superblock was found.");
+      return Ok(());
+    }
+
+    println!(
+      "{:>8}  {:>10}  {:>12}  {:>14}  {:>12}  {:<36}  {:<8}  Last written",
+      "Group", "Block size", "Block", "Offset", "Blocks", "UUID", "Checksum"
+    );
+    for backup in backups {
+      println!(
+        "{:>8}  {:>10}  {:>12}  {:>14}  {:>12}  {:<36}  {:<8}  {}",
+        backup.group,
+        backup.sb.get_block_size(),
+        backup.block,
+        self.offset + backup.get_offset(),
+        backup.sb.get_blocks_count(),
+        backup.sb.uuid.to_string(),
+        checksum_status(&backup.sb),
+        backup.sb.wtime
+      );
+    }
+
+    Ok(())
+  }
+}
+
+/// Describes whether a superblock passes its checksum.
+pub(crate) fn checksum_status(sb: &Superblock) -> &'static str
+{
+  if !sb.feature_metadata_csum() {
+    "none"
+  } else if sb.check_checksum().is_none() {
+    "valid"
+  } else {
+    "invalid"
+  }
+}
@@ -0,0 +1,91 @@
+use crate::{die, error, info};
+use recover::ext4::{carve, file_sys::Error, group_desc::Flags as GroupFlags, FileSystem, GroupDesc};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+pub(crate) struct Carve
+{
+  pub(crate) path: PathBuf,
+  pub(crate) offset: u64,
+  pub(crate) backup: Option<u32>,
+  pub(crate) out: PathBuf,
+}
+
+impl Carve
+{
+  pub(crate) fn run(&self)
+  {
+    if let Err(err) = self.carve() {
+      die!("{}", err);
+    }
+  }
+
+  fn carve(&self) -> Result<(), Error>
+  {
+    let img = File::open(self.path.as_path())?;
+    let mut fs = match self.backup {
+      Some(group) => FileSystem::from_backup(img, self.offset, group)?,
+      None => FileSystem::new(img, self.offset)?,
+    };
+    let block_size: u64 = fs.sb.get_block_size() as u64;
+    self.check_bitmaps(&mut fs);
+    fs::create_dir_all(&self.out)?;
+
+    let mut manifest = File::create(self.out.join("manifest.tsv"))?;
+    writeln!(manifest, "file\tformat\tblock\toffset\tsize\tstatus")?;
+    let mut carved: usize = 0;
+    for file in fs.carve(carve::default_signatures()) {
+      let file = match file {
+        Ok(file) => file,
+        Err(err) => {
+          error!("{}", err);
+          continue;
+        }
+      };
+      // Files are named after where they start in the image, which is unique.
+      let offset: u64 = self.offset + file.block * block_size;
+      let name: String = format!("{}.{}", offset, file.extension);
+      fs::write(self.out.join(&name), &file.data)?;
+      writeln!(
+        manifest,
+        "{}\t{}\t{}\t{}\t{}\t{}",
+        name,
+        file.format,
+        file.block,
+        offset,
+        file.data.len(),
+        if file.complete { "complete" } else { "truncated" }
+      )?;
+      carved += 1;
+    }
+    info!("Carved {} files into {}.", carved, self.out.display());
+
+    Ok(())
+  }
+
+  /// Warns about the groups whose block bitmap does not match its checksum, as the blocks it marks
+  /// as free may have been allocated since.
+  fn check_bitmaps(&self, fs: &mut FileSystem<File>)
+  {
+    let descs: Vec<Result<GroupDesc, Error>> = fs.iter_group_descriptors().collect();
+    for (group, desc) in descs.iter().enumerate() {
+      // Groups whose descriptor cannot be read are reported by the carver.
+      let desc: &GroupDesc = match desc {
+        Ok(desc) if !desc.flags.contains(GroupFlags::BLOCK_UNINIT) => desc,
+        _ => continue,
+      };
+      match fs.read_block_bitmap(desc) {
+        Ok(bitmap) => {
+          if let Some(err) = bitmap.check_checksum() {
+            error!("Group {} block bitmap checksum error: {}", group, err);
+            info!("Some of the free blocks of group {} may be in use.", group);
+          }
+        }
+        Err(err) => {
+          error!("Group {} block bitmap: {}", group, err);
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,86 @@
+use crate::{die, error};
+use recover::ext4::{file_sys::Error, FileSystem};
+use std::fs::File;
+use std::path::PathBuf;
+
+pub(crate) struct Deleted
+{
+  pub(crate) path: PathBuf,
+  pub(crate) offset: u64,
+  pub(crate) backup: Option<u32>,
+}
+
+impl Deleted
+{
+  pub(crate) fn run(&self)
+  {
+    if let Err(err) = self.list() {
+      die!("{}", err);
+    }
+  }
+
+  fn list(&self) -> Result<(), Error>
+  {
+    let img = File::open(self.path.as_path())?;
+    let mut fs = match self.backup {
+      Some(group) => FileSystem::from_backup(img, self.offset, group)?,
+      None => FileSystem::new(img, self.offset)?,
+    };
+    let block_size: u64 = fs.sb.get_block_size() as u64;
+
+    println!(
+      "{:>10}  {:<6}  {:<10}  {:>12}  {:>10}  {:<23}  {:<8}  Recoverable",
+      "Inode", "Slot", "Mode", "Size", "Blocks", "Deleted", "Checksum"
+    );
+    for deleted in fs.iter_deleted_inodes() {
+      let deleted = match deleted {
+        Ok(deleted) => deleted,
+        Err(err) => {
+          error!("{}", err);
+          continue;
+        }
+      };
+      let blocks: u64 = deleted.extents.iter().map(|extent| extent.len as u64).sum();
+      println!(
+        "{:>10}  {:<6}  {:<10}  {:>12}  {:>10}  {:<23}  {:<8}  {}",
+        deleted.ino,
+        // The slot is only as trustworthy as the bitmap it comes from.
+        match (deleted.in_use, deleted.bitmap_valid) {
+          (true, true) => "used",
+          (true, false) => "used?",
+          (false, true) => "free",
+          (false, false) => "free?",
+        },
+        deleted.inode.mode.to_string(),
+        deleted.inode.size,
+        blocks,
+        if deleted.inode.dtime.timestamp() == 0 {
+          String::from("-")
+        } else {
+          deleted.inode.dtime.to_string()
+        },
+        // Garbage in an inode slot fails its checksum, while the kernel updates it on deletion.
+        match deleted.inode.calculated_checksum {
+          None => "none",
+          Some(_) if deleted.inode.check_checksum().is_none() => "valid",
+          Some(_) => "invalid",
+        },
+        if deleted.is_in_inode() {
+          String::from("yes (in the inode)")
+        } else if deleted.is_recoverable() {
+          format!("yes ({} bytes mapped)", (blocks * block_size).min(deleted.inode.size))
+        } else {
+          String::from("no")
+        }
+      );
+      if !deleted.unreadable.is_empty() {
+        error!(
+          "Inode {}: unable to read blocks {:?} of its mapping, which are left out.",
+          deleted.ino, deleted.unreadable
+        );
+      }
+    }
+
+    Ok(())
+  }
+}
@@ -1,5 +1,5 @@
 use crate::{die, error, info};
-use recover::ext4::FileSystem;
+use recover::ext4::{file_sys::Error, group_desc::Flags as GroupFlags, FileSystem, GroupDesc};
 use std::fs::File;
 use std::io;
 use std::path::PathBuf;
@@ -8,6 +8,7 @@ pub(crate) struct Dump
 {
   pub(crate) path: PathBuf,
   pub(crate) offset: u64,
+  pub(crate) backup: Option<u32>,
 }
 
 impl Dump
@@ -23,17 +24,62 @@ impl Dump
   {
     let img = File::open(self.path.as_path())?;
 
-    let fs = FileSystem::new(img, self.offset).unwrap_or_else(|err| {
+    let mut fs = match self.backup {
+      Some(group) => FileSystem::from_backup(img, self.offset, group),
+      None => FileSystem::new(img, self.offset),
+    }
+    .unwrap_or_else(|err| {
       die!("{}", err);
     });
 
     if let Some(err) = fs.sb.check_signature() {
       error!("Magic error: {}", err);
       info!("This dump information may not be accurate.");
+    } else if let Some(err) = fs.sb.check_checksum() {
+      error!("Checksum error: {}", err);
+      info!("This dump information may not be accurate.");
     }
 
     print!("{}", fs.sb);
 
+    let descs: Vec<Result<GroupDesc, Error>> = fs.iter_group_descriptors().collect();
+    for (group, desc) in descs.iter().enumerate() {
+      let desc: &GroupDesc = match desc {
+        Ok(desc) => desc,
+        Err(err) => {
+          error!("Group {}: {}", group, err);
+          continue;
+        }
+      };
+      if let Some(err) = desc.check_checksum() {
+        error!("Group {} descriptor checksum error: {}", group, err);
+      }
+      if !desc.flags.contains(GroupFlags::BLOCK_UNINIT) {
+        match fs.read_block_bitmap(desc) {
+          Ok(bitmap) => {
+            if let Some(err) = bitmap.check_checksum() {
+              error!("Group {} block bitmap checksum error: {}", group, err);
+            }
+          }
+          Err(err) => {
+            error!("Group {} block bitmap: {}", group, err);
+          }
+        }
+      }
+      if !desc.flags.contains(GroupFlags::INODE_UNINIT) {
+        match fs.read_inode_bitmap(desc) {
+          Ok(bitmap) => {
+            if let Some(err) = bitmap.check_checksum() {
+              error!("Group {} inode bitmap checksum error: {}", group, err);
+            }
+          }
+          Err(err) => {
+            error!("Group {} inode bitmap: {}", group, err);
+          }
+        }
+      }
+    }
+
     Ok(())
   }
 }
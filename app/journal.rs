@@ -0,0 +1,62 @@
+use crate::die;
+use recover::ext4::{file_sys::Error, jbd2::Transaction, FileSystem};
+use std::fs::File;
+use std::path::PathBuf;
+
+pub(crate) struct Journal
+{
+  pub(crate) path: PathBuf,
+  pub(crate) offset: u64,
+  pub(crate) backup: Option<u32>,
+}
+
+impl Journal
+{
+  pub(crate) fn run(&self)
+  {
+    if let Err(err) = self.list() {
+      die!("{}", err);
+    }
+  }
+
+  fn list(&self) -> Result<(), Error>
+  {
+    let img = File::open(self.path.as_path())?;
+    let mut fs = match self.backup {
+      Some(group) => FileSystem::from_backup(img, self.offset, group)?,
+      None => FileSystem::new(img, self.offset)?,
+    };
+    let journal = fs.open_journal()?;
+
+    println!("Journal UUID:             {}", journal.sb.uuid);
+    println!("Journal superblock type:  {}", journal.sb.header.block_type);
+    println!("Journal features:         {}", journal.sb.feature_compat);
+    println!("Journal incompat:         {}", journal.sb.feature_incompat);
+    println!("Journal size:             {} blocks", journal.sb.max_len);
+    println!("Journal first block:      {}", journal.sb.first);
+    println!("Journal sequence:         {}", journal.sb.sequence);
+    println!("Journal start:            {}", journal.sb.start);
+    println!();
+
+    let transactions: Vec<Transaction> = fs.read_transactions(&journal)?;
+    for transaction in &transactions {
+      match transaction.commit_time {
+        Some(time) => println!("Transaction {} committed at {}", transaction.sequence, time),
+        None => println!("Transaction {} (not committed)", transaction.sequence),
+      }
+      for logged in &transaction.blocks {
+        println!(
+          "  block {} logged in journal block {}",
+          logged.tag.block, logged.journal_block
+        );
+      }
+      if !transaction.revoked.is_empty() {
+        let revoked: Vec<String> = transaction.revoked.iter().map(|block| block.to_string()).collect();
+        println!("  revoked: {}", revoked.join(", "));
+      }
+    }
+    println!("{} transactions found.", transactions.len());
+
+    Ok(())
+  }
+}
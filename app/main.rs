@@ -1,10 +1,36 @@
-use clap::{App, Arg};
+use clap::{App, Arg, ArgMatches};
+use recover::ext4::FileSystem;
+use std::fs::File;
 
 mod log;
 
+mod backups;
+pub(crate) use backups::Backups;
+
+mod carve;
+pub(crate) use carve::Carve;
+
+mod deleted;
+pub(crate) use deleted::Deleted;
+
 mod dump;
 pub(crate) use dump::Dump;
 
+mod journal;
+pub(crate) use journal::Journal;
+
+mod partitions;
+pub(crate) use partitions::Partitions;
+
+mod scan;
+pub(crate) use scan::Scan;
+
+mod stat;
+pub(crate) use stat::Stat;
+
+mod undelete;
+pub(crate) use undelete::Undelete;
+
 fn main()
 {
   let matches = App::new("Recover")
@@ -22,26 +48,195 @@ fn main()
             .value_name("PATH")
             .required(true),
         )
+        .args(&get_location_args(true)),
+    )
+    .subcommand(
+      App::new("deleted")
+        .about("Lists the deleted inodes of an ext4 partition")
+        .author("B. Howe <37745048+byhowe@users.noreply.github.com>")
+        .arg(
+          Arg::with_name("path")
+            .help("path to the partition")
+            .takes_value(true)
+            .value_name("PATH")
+            .required(true),
+        )
+        .args(&get_location_args(true)),
+    )
+    .subcommand(
+      App::new("backups")
+        .about("Searches an ext4 partition for backup superblocks")
+        .author("B. Howe <37745048+byhowe@users.noreply.github.com>")
+        .arg(
+          Arg::with_name("path")
+            .help("path to the partition")
+            .takes_value(true)
+            .value_name("PATH")
+            .required(true),
+        )
+        .args(&get_location_args(false)),
+    )
+    .subcommand(
+      App::new("carve")
+        .about("Carves files out of the free blocks of an ext4 partition")
+        .author("B. Howe <37745048+byhowe@users.noreply.github.com>")
+        .arg(
+          Arg::with_name("path")
+            .help("path to the partition")
+            .takes_value(true)
+            .value_name("PATH")
+            .required(true),
+        )
         .arg(
-          Arg::with_name("offset")
-            .help("offset from the start of the image")
+          Arg::with_name("out")
+            .help("directory to write the carved files to")
             .takes_value(true)
-            .default_value("0")
-            .value_name("OFFSET")
-            .long("offset")
-            .short("s"),
+            .value_name("DIR")
+            .long("out")
+            .short("o")
+            .required(true),
+        )
+        .args(&get_location_args(true)),
+    )
+    .subcommand(
+      App::new("journal")
+        .about("Lists the transactions in the journal of an ext4 partition")
+        .author("B. Howe <37745048+byhowe@users.noreply.github.com>")
+        .arg(
+          Arg::with_name("path")
+            .help("path to the partition")
+            .takes_value(true)
+            .value_name("PATH")
+            .required(true),
+        )
+        .args(&get_location_args(true)),
+    )
+    .subcommand(
+      App::new("partitions")
+        .about("Lists the partitions of a whole-disk image")
+        .author("B. Howe <37745048+byhowe@users.noreply.github.com>")
+        .arg(
+          Arg::with_name("path")
+            .help("path to the disk image")
+            .takes_value(true)
+            .value_name("PATH")
+            .required(true),
+        ),
+    )
+    .subcommand(
+      App::new("scan")
+        .about("Scans a raw image for the superblocks of lost ext4 filesystems")
+        .author("B. Howe <37745048+byhowe@users.noreply.github.com>")
+        .arg(
+          Arg::with_name("path")
+            .help("path to the image")
+            .takes_value(true)
+            .value_name("PATH")
+            .required(true),
         ),
     )
+    .subcommand(
+      App::new("stat")
+        .about("Shows information about a file in an ext4 partition")
+        .author("B. Howe <37745048+byhowe@users.noreply.github.com>")
+        .arg(
+          Arg::with_name("path")
+            .help("path to the partition")
+            .takes_value(true)
+            .value_name("PATH")
+            .required(true),
+        )
+        .arg(
+          Arg::with_name("file")
+            .help("path of the file inside the partition")
+            .takes_value(true)
+            .value_name("FILE")
+            .required(true),
+        )
+        .args(&get_location_args(true)),
+    )
+    .subcommand(
+      App::new("undelete")
+        .about("Extracts the recoverable deleted files of an ext4 partition")
+        .author("B. Howe <37745048+byhowe@users.noreply.github.com>")
+        .arg(
+          Arg::with_name("path")
+            .help("path to the partition")
+            .takes_value(true)
+            .value_name("PATH")
+            .required(true),
+        )
+        .arg(
+          Arg::with_name("out")
+            .help("directory to write the recovered files to")
+            .takes_value(true)
+            .value_name("DIR")
+            .long("out")
+            .short("o")
+            .required(true),
+        )
+        .arg(
+          Arg::with_name("journal")
+            .help("looks up copies of the deleted inodes in the journal")
+            .long("journal")
+            .short("j"),
+        )
+        .args(&get_location_args(true)),
+    )
     .get_matches();
 
   match matches.subcommand() {
     ("dump", Some(subm)) => Dump {
       path: subm.value_of("path").unwrap().into(),
-      offset: subm
-        .value_of("offset")
-        .unwrap()
-        .parse::<u64>()
-        .unwrap_or_else(|err| die!("Unable to parse OFFSET as a valid u64: {}", err)),
+      offset: get_offset(subm),
+      backup: get_backup(subm),
+    }
+    .run(),
+    ("deleted", Some(subm)) => Deleted {
+      path: subm.value_of("path").unwrap().into(),
+      offset: get_offset(subm),
+      backup: get_backup(subm),
+    }
+    .run(),
+    ("backups", Some(subm)) => Backups {
+      path: subm.value_of("path").unwrap().into(),
+      offset: get_offset(subm),
+    }
+    .run(),
+    ("carve", Some(subm)) => Carve {
+      path: subm.value_of("path").unwrap().into(),
+      offset: get_offset(subm),
+      backup: get_backup(subm),
+      out: subm.value_of("out").unwrap().into(),
+    }
+    .run(),
+    ("journal", Some(subm)) => Journal {
+      path: subm.value_of("path").unwrap().into(),
+      offset: get_offset(subm),
+      backup: get_backup(subm),
+    }
+    .run(),
+    ("partitions", Some(subm)) => Partitions {
+      path: subm.value_of("path").unwrap().into(),
+    }
+    .run(),
+    ("scan", Some(subm)) => Scan {
+      path: subm.value_of("path").unwrap().into(),
+    }
+    .run(),
+    ("stat", Some(subm)) => Stat {
+      path: subm.value_of("path").unwrap().into(),
+      offset: get_offset(subm),
+      backup: get_backup(subm),
+      file: subm.value_of("file").unwrap().into(),
+    }
+    .run(),
+    ("undelete", Some(subm)) => Undelete {
+      path: subm.value_of("path").unwrap().into(),
+      offset: get_offset(subm),
+      backup: get_backup(subm),
+      out: subm.value_of("out").unwrap().into(),
+      journal: subm.is_present("journal"),
     }
     .run(),
     _ => {
@@ -50,3 +245,70 @@ fn main()
     }
   }
 }
+
+/// Builds the arguments that locate the filesystem in the image: the partition, the offset and,
+/// when `backup` is set, the backup superblock to open it with.
+fn get_location_args<'a, 'b>(backup: bool) -> Vec<Arg<'a, 'b>>
+{
+  let mut args: Vec<Arg<'a, 'b>> = Vec::new();
+  if backup {
+    args.push(
+      Arg::with_name("backup")
+        .help("block group of the backup superblock to open the partition with")
+        .takes_value(true)
+        .value_name("GROUP")
+        .long("backup")
+        .short("b"),
+    );
+  }
+  args.push(
+    Arg::with_name("partition")
+      .help("number of the partition to open in a whole-disk image")
+      .takes_value(true)
+      .value_name("NUMBER")
+      .long("partition")
+      .short("p"),
+  );
+  args.push(
+    Arg::with_name("offset")
+      .help("offset from the start of the image")
+      .takes_value(true)
+      .default_value("0")
+      .value_name("OFFSET")
+      .long("offset")
+      .short("s"),
+  );
+  args
+}
+
+/// Returns the block group of the backup superblock to open the filesystem with, if one was
+/// chosen.
+fn get_backup(subm: &ArgMatches) -> Option<u32>
+{
+  subm.value_of("backup").map(|group| {
+    group
+      .parse::<u32>()
+      .unwrap_or_else(|err| die!("Unable to parse GROUP as a valid u32: {}", err))
+  })
+}
+
+/// Returns the offset of the filesystem in the image. When a partition is chosen, OFFSET counts
+/// from the start of the partition.
+fn get_offset(subm: &ArgMatches) -> u64
+{
+  let offset: u64 = subm
+    .value_of("offset")
+    .unwrap()
+    .parse::<u64>()
+    .unwrap_or_else(|err| die!("Unable to parse OFFSET as a valid u64: {}", err));
+  let number: u32 = match subm.value_of("partition") {
+    Some(number) => number
+      .parse::<u32>()
+      .unwrap_or_else(|err| die!("Unable to parse NUMBER as a valid u32: {}", err)),
+    None => return offset,
+  };
+
+  let path: &str = subm.value_of("path").unwrap();
+  let mut img = File::open(path).unwrap_or_else(|err| die!("Unable to open {}: {}", path, err));
+  offset + FileSystem::get_partition_offset(&mut img, number).unwrap_or_else(|err| die!("{}", err))
+}
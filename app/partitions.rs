@@ -0,0 +1,55 @@
+use crate::{die, info};
+use recover::partition::{Error, PartitionTable, Scheme};
+use std::fs::File;
+use std::path::PathBuf;
+
+pub(crate) struct Partitions
+{
+  pub(crate) path: PathBuf,
+}
+
+impl Partitions
+{
+  pub(crate) fn run(&self)
+  {
+    if let Err(err) = self.list() {
+      die!("{}", err);
+    }
+  }
+
+  fn list(&self) -> Result<(), Error>
+  {
+    let mut img = File::open(self.path.as_path())?;
+    let table = PartitionTable::new(&mut img)?;
+
+    match &table.scheme {
+      Scheme::Mbr => println!("Partition table:  MBR"),
+      Scheme::Gpt { disk_guid, from_backup } => {
+        println!("Partition table:  GPT");
+        println!("Disk GUID:        {}", disk_guid);
+        if *from_backup {
+          info!("The primary GPT is damaged, the backup was used instead.");
+        }
+      }
+    }
+    println!();
+
+    println!(
+      "{:>6}  {:>14}  {:>14}  {:<4}  {:<56}  Name",
+      "Number", "Offset", "Size", "ext", "Type"
+    );
+    for partition in &table.partitions {
+      println!(
+        "{:>6}  {:>14}  {:>14}  {:<4}  {:<56}  {}",
+        partition.number,
+        partition.offset,
+        partition.size,
+        if partition.ext { "yes" } else { "no" },
+        partition.partition_type.to_string(),
+        partition.name
+      );
+    }
+
+    Ok(())
+  }
+}
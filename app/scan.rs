@@ -0,0 +1,58 @@
+use crate::{backups::checksum_status, die, info};
+use recover::ext4::{file_sys::Error, FileSystem};
+use std::fs::File;
+use std::path::PathBuf;
+
+pub(crate) struct Scan
+{
+  pub(crate) path: PathBuf,
+}
+
+impl Scan
+{
+  pub(crate) fn run(&self)
+  {
+    if let Err(err) = self.scan() {
+      die!("{}", err);
+    }
+  }
+
+  fn scan(&self) -> Result<(), Error>
+  {
+    let mut img = File::open(self.path.as_path())?;
+    let found = FileSystem::scan_superblocks(&mut img)?;
+    if found.is_empty() {
+      info!("No superblock was found.");
+      return Ok(());
+    }
+
+    for fs in found {
+      let sb = &fs.hits[0].sb;
+      println!("Filesystem at offset {}", fs.offset);
+      println!("  UUID:         {}", sb.uuid);
+      println!("  Volume name:  {}", sb.volume_name.trim_end_matches('\0'));
+      println!("  Block size:   {}", sb.get_block_size());
+      println!(
+        "  Blocks:       {}{}",
+        sb.get_blocks_count(),
+        if fs.truncated {
+          " (more than the image holds)"
+        } else {
+          ""
+        }
+      );
+      println!("  Superblocks:");
+      for hit in &fs.hits {
+        println!(
+          "    group {:>6} at offset {} (checksum {})",
+          hit.group,
+          hit.offset,
+          checksum_status(&hit.sb)
+        );
+      }
+      println!();
+    }
+
+    Ok(())
+  }
+}
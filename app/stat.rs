@@ -0,0 +1,127 @@
+use crate::{die, error};
+use recover::ext4::{
+  file_sys::Error,
+  inode::{Flags, Mode},
+  xattr::{Acl, Xattr},
+  FileSystem, Inode,
+};
+use std::fs::File;
+use std::path::PathBuf;
+
+pub(crate) struct Stat
+{
+  pub(crate) path: PathBuf,
+  pub(crate) offset: u64,
+  pub(crate) backup: Option<u32>,
+  pub(crate) file: String,
+}
+
+impl Stat
+{
+  pub(crate) fn run(&self)
+  {
+    if let Err(err) = self.stat() {
+      die!("{}", err);
+    }
+  }
+
+  fn stat(&self) -> Result<(), Error>
+  {
+    let img = File::open(self.path.as_path())?;
+    let mut fs = match self.backup {
+      Some(group) => FileSystem::from_backup(img, self.offset, group)?,
+      None => FileSystem::new(img, self.offset)?,
+    };
+    let (ino, inode): (u32, Inode) = fs.lookup_nofollow(&self.file)?;
+
+    let xattrs: Vec<Xattr> = fs.read_xattrs(&inode).unwrap_or_else(|err| {
+      error!("Unable to read the extended attributes: {}", err);
+      Vec::new()
+    });
+    let acl = |name: &str| {
+      xattrs
+        .iter()
+        .find(|xattr| xattr.get_name() == name)
+        .and_then(|xattr| xattr.get_acl())
+    };
+
+    // The link itself is shown rather than what it points to, as with lstat.
+    if inode.mode.file_type_flags() == Mode::SYMLINK {
+      println!("File:        {} -> {}", self.file, fs.read_symlink(&inode)?);
+    } else {
+      println!("File:        {}", self.file);
+    }
+    println!(
+      "Inode:       {}   Links: {}   Generation: {}",
+      ino, inode.links_count, inode.generation
+    );
+    println!(
+      "Size:        {}   Blocks: {}",
+      inode.size,
+      inode.get_blocks_count(&fs.sb)
+    );
+    let access = acl(Acl::ACCESS_NAME);
+    println!(
+      "Mode:        {}{} ({:04o})   Uid: {}   Gid: {}",
+      inode.mode,
+      if access.is_some() { "+" } else { "" },
+      inode.mode.bits() & 0o7777,
+      inode.uid,
+      inode.gid
+    );
+    for (label, acl) in [("Access ACL:", access), ("Default ACL:", acl(Acl::DEFAULT_NAME))] {
+      match acl {
+        Some(Ok(acl)) => println!("{:<12} {}", label, acl),
+        Some(Err(err)) => println!("{:<12} {}", label, err),
+        None => {}
+      }
+    }
+    println!("Flags:       {:?}", inode.flags);
+    if inode.calculated_checksum.is_some() {
+      match inode.check_checksum() {
+        None => println!("Checksum:    {:#010X} (valid)", inode.checksum),
+        Some(err) => println!("Checksum:    {:#010X} (invalid: {})", inode.checksum, err),
+      }
+    }
+    println!("Access:      {}", inode.atime);
+    println!("Modify:      {}", inode.mtime);
+    println!("Change:      {}", inode.ctime);
+    if inode.extra_isize != 0 {
+      println!("Birth:       {}", inode.crtime);
+    }
+    if inode.dtime.timestamp() != 0 {
+      println!("Deleted:     {}", inode.dtime);
+    }
+    for xattr in xattrs.iter().filter(|xattr| xattr.get_acl().is_none()) {
+      if xattr.value.len() > 64 {
+        println!("Xattr:       {} ({} bytes)", xattr.get_name(), xattr.value.len());
+      } else {
+        println!(
+          "Xattr:       {} = {:?}",
+          xattr.get_name(),
+          String::from_utf8_lossy(&xattr.value)
+        );
+      }
+    }
+
+    // The blocks the inode owns carry checksums of their own, which tell stale ones apart.
+    if inode.flags.contains(Flags::EXTENTS) {
+      for block in fs.read_extent_blocks(&inode)? {
+        if let Some(err) = block.check_checksum() {
+          error!("Extent block {} checksum error: {}", block.block, err);
+        }
+      }
+    }
+    if inode.mode.file_type_flags() == Mode::DIR {
+      let mut entries = fs.iter_dir(inode)?;
+      entries.by_ref().for_each(drop);
+      for block in entries.get_blocks() {
+        if let Some(err) = block.check_checksum() {
+          error!("Directory block {} checksum error: {}", block.index, err);
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
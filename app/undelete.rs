@@ -0,0 +1,242 @@
+use crate::{die, error, info};
+use recover::ext4::{
+  carve::default_signatures,
+  file_sys::{iters::DeletedInode, Error, FileReader},
+  inode::Mode,
+  jbd2::{Journal, Transaction},
+  FileSystem,
+};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+pub(crate) struct Undelete
+{
+  pub(crate) path: PathBuf,
+  pub(crate) offset: u64,
+  pub(crate) backup: Option<u32>,
+  pub(crate) out: PathBuf,
+  pub(crate) journal: bool,
+}
+
+impl Undelete
+{
+  pub(crate) fn run(&self)
+  {
+    if let Err(err) = self.undelete() {
+      die!("{}", err);
+    }
+  }
+
+  fn undelete(&self) -> Result<(), Error>
+  {
+    let img = File::open(self.path.as_path())?;
+    let mut fs = match self.backup {
+      Some(group) => FileSystem::from_backup(img, self.offset, group)?,
+      None => FileSystem::new(img, self.offset)?,
+    };
+    let block_size: u64 = fs.sb.get_block_size() as u64;
+    fs::create_dir_all(&self.out)?;
+
+    // The scan borrows the filesystem, so everything is gathered before any file is read.
+    let mut deleted: Vec<DeletedInode> = Vec::new();
+    for inode in fs.iter_deleted_inodes() {
+      match inode {
+        Ok(inode) => deleted.push(inode),
+        Err(err) => {
+          error!("{}", err);
+        }
+      }
+    }
+
+    // Without a usable journal, the inodes are still recovered from what they hold themselves.
+    let journal: Option<(Journal, Vec<Transaction>)> = if self.journal {
+      match fs.open_journal().and_then(|journal| {
+        let transactions: Vec<Transaction> = fs.read_transactions(&journal)?;
+        Ok((journal, transactions))
+      }) {
+        Ok(journal) => Some(journal),
+        Err(err) => {
+          error!("Unable to read the journal: {}", err);
+          None
+        }
+      }
+    } else {
+      None
+    };
+
+    let mut manifest = File::create(self.out.join("manifest.tsv"))?;
+    writeln!(
+      manifest,
+      "inode\tfile\tsource\tstatus\tsize\trecovered\tmode\tuid\tgid\tmtime\tdtime\ttarget\terror"
+    )?;
+    let mut recovered: usize = 0;
+    for mut deleted in deleted {
+      // Deletion clears the inode's block map, but the journal may still hold a copy of the inode
+      // from before, whose mapping is then preferred.
+      let dtime = deleted.inode.dtime;
+      let mut source: String = String::from("inode");
+      if let Some((journal, transactions)) = &journal {
+        match fs.recover_inode_from_journal(journal, transactions, deleted.ino, &deleted.inode) {
+          Ok(Some((copy, extents))) if !extents.is_empty() => {
+            source = format!("journal:{}", copy.sequence);
+            deleted.inode = copy.inode;
+            deleted.extents = extents;
+          }
+          Ok(_) => {}
+          Err(err) => {
+            error!("Unable to look up inode {} in the journal: {}", deleted.ino, err);
+          }
+        }
+      }
+      if !deleted.is_recoverable() || deleted.inode.mode.file_type_flags() == Mode::DIR {
+        continue;
+      }
+      if source == "inode" && !deleted.unreadable.is_empty() {
+        error!(
+          "Inode {}: unable to read blocks {:?} of its mapping, which are left out.",
+          deleted.ino, deleted.unreadable
+        );
+      }
+      // Inline data and the targets of fast symbolic links are whole in the inode, while anything
+      // else is only as complete as its extents. Nothing past the last mapped block is worth
+      // writing, however large the inode claims to be.
+      let size: u64 = deleted.inode.size;
+      let (mapped_end, mapped): (u64, u64) = if deleted.is_in_inode() {
+        (size, size)
+      } else {
+        let end: u64 = deleted
+          .extents
+          .iter()
+          .map(|extent| extent.block as u64 + extent.len as u64)
+          .max()
+          .unwrap_or(0);
+        let mapped: u64 = deleted
+          .extents
+          .iter()
+          .map(|extent| extent.len as u64 * block_size)
+          .sum::<u64>();
+        ((end * block_size).min(size), mapped.min(size))
+      };
+      let status: &str = if mapped == size { "full" } else { "partial" };
+
+      // A symbolic link is recorded with its target rather than written out as a file, and a file
+      // that cannot be read is recorded as failed rather than ending the whole run.
+      let written: Result<(String, String), Error> = if deleted.inode.mode.file_type_flags() == Mode::SYMLINK {
+        fs.read_symlink_from_extents(&deleted.inode, deleted.extents.clone())
+          .map(|target| (String::from("-"), target.to_string()))
+      } else {
+        self
+          .write_file(&mut fs, &deleted, mapped_end)
+          .map(|name| (name, String::new()))
+      };
+      let (name, target, status, reason): (String, String, &str, String) = match written {
+        Ok((name, target)) => (name, target, status, String::new()),
+        Err(err) => {
+          error!("Unable to recover inode {}: {}", deleted.ino, err);
+          (String::from("-"), String::new(), "failed", err.to_string())
+        }
+      };
+
+      writeln!(
+        manifest,
+        "{}\t{}\t{}\t{}\t{}\t{}\t{:04o}\t{}\t{}\t{}\t{}\t{}\t{}",
+        deleted.ino,
+        name,
+        source,
+        status,
+        size,
+        if status == "failed" { 0 } else { mapped },
+        deleted.inode.mode.bits() & 0o7777,
+        deleted.inode.uid,
+        deleted.inode.gid,
+        deleted.inode.mtime,
+        dtime,
+        target,
+        reason
+      )?;
+      if status != "failed" {
+        recovered += 1;
+      }
+    }
+    info!("Recovered {} files into {}.", recovered, self.out.display());
+
+    Ok(())
+  }
+
+  /// Writes the first `len` bytes the inode maps into a file named after it, and returns the
+  /// name. A file left incomplete by an error is removed.
+  fn write_file(&self, fs: &mut FileSystem<File>, deleted: &DeletedInode, len: u64) -> Result<String, Error>
+  {
+    // Signatures are matched against the start of a block, which is where a file starts.
+    let block_size: u64 = fs.sb.get_block_size() as u64;
+    let mut reader = FileReader::from_extents(fs, deleted.inode.clone(), deleted.extents.clone())?;
+    let mut head: Vec<u8> = Vec::new();
+    (&mut reader).take(len.min(block_size)).read_to_end(&mut head)?;
+    let name: String = format!("{}.{}", deleted.ino, guess_extension(&head));
+    let path: PathBuf = self.out.join(&name);
+    let mut file = File::create(&path)?;
+    let written: io::Result<u64> = file
+      .write_all(&head)
+      .and_then(|_| io::copy(&mut reader.take(len - head.len() as u64), &mut file));
+    drop(file);
+    if let Err(err) = written {
+      let _ = fs::remove_file(&path);
+      return Err(err.into());
+    }
+    if let Err(err) = restore_metadata(&path, deleted) {
+      error!("Unable to restore the metadata of {}: {}", name, err);
+    }
+    Ok(name)
+  }
+}
+
+#[cfg(unix)]
+fn restore_metadata(path: &Path, deleted: &DeletedInode) -> io::Result<()>
+{
+  use std::os::unix::{ffi::OsStrExt, fs::PermissionsExt};
+
+  let mode: u32 = (deleted.inode.mode.bits() & 0o7777) as u32;
+  fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+
+  let times: [libc::timeval; 2] = [
+    libc::timeval {
+      tv_sec: deleted.inode.atime.timestamp() as libc::time_t,
+      tv_usec: 0,
+    },
+    libc::timeval {
+      tv_sec: deleted.inode.mtime.timestamp() as libc::time_t,
+      tv_usec: 0,
+    },
+  ];
+  let mut c_path: Vec<u8> = path.as_os_str().as_bytes().to_vec();
+  c_path.push(0);
+  if unsafe { libc::utimes(c_path.as_ptr() as *const libc::c_char, times.as_ptr()) } == 0 {
+    Ok(())
+  } else {
+    Err(io::Error::last_os_error())
+  }
+}
+
+#[cfg(not(unix))]
+fn restore_metadata(path: &Path, deleted: &DeletedInode) -> io::Result<()>
+{
+  let mut permissions: fs::Permissions = fs::metadata(path)?.permissions();
+  permissions.set_readonly(!deleted.inode.mode.contains(Mode::OWNER_W));
+  fs::set_permissions(path, permissions)
+}
+
+/// Guesses a file extension from the first bytes of a file, through the formats the carver knows.
+fn guess_extension(head: &[u8]) -> String
+{
+  if let Some(signature) = default_signatures().iter().find(|signature| signature.matches(head)) {
+    return signature.extension(head).to_string();
+  }
+  String::from(match std::str::from_utf8(head) {
+    // The head may end in the middle of a character.
+    Err(err) if err.error_len().is_some() => "bin",
+    _ if head.iter().any(|&b| b < 0x20 && !b"\t\n\r".contains(&b)) => "bin",
+    _ if head.is_empty() => "bin",
+    _ => "txt",
+  })
+}
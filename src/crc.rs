@@ -0,0 +1,49 @@
+/// Lookup table of the reflected CRC-32 used by zlib, Ethernet and the GPT.
+const CRC32_TABLE: [u32; 256] = crc_table(0xEDB8_8320);
+/// Lookup table of the reflected CRC-16 (ARC) used by the gdt_csum group descriptor checksums. The
+/// polynomial fits in 16 bits, and so does every entry.
+const CRC16_TABLE: [u32; 256] = crc_table(0xA001);
+/// Lookup table of the reflected CRC-32C (Castagnoli) used by ext4 and jbd2 metadata checksums.
+const CRC32C_TABLE: [u32; 256] = crc_table(0x82F6_3B78);
+
+const fn crc_table(poly: u32) -> [u32; 256]
+{
+  let mut table: [u32; 256] = [0; 256];
+  let mut i: usize = 0;
+  while i < 256 {
+    let mut crc: u32 = i as u32;
+    let mut bit: u32 = 0;
+    while bit < 8 {
+      crc = if crc & 1 != 0 { (crc >> 1) ^ poly } else { crc >> 1 };
+      bit += 1;
+    }
+    table[i] = crc;
+    i += 1;
+  }
+  table
+}
+
+/// Computes the CRC-32 of `data` the way zlib does.
+pub fn crc32(data: &[u8]) -> u32
+{
+  !data.iter().fold(!0, |crc: u32, &byte| {
+    CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+  })
+}
+
+/// Continues a CRC-32C from `crc` over `data`. Like the kernel's `crc32c_le`, there is no implicit
+/// inversion on either end, so ext4 checksums start from `!0` and are stored as they come out.
+pub fn crc32c(crc: u32, data: &[u8]) -> u32
+{
+  data.iter().fold(crc, |crc: u32, &byte| {
+    CRC32C_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+  })
+}
+
+/// Continues a CRC-16 from `crc` over `data`, like the kernel's `crc16`.
+pub fn crc16(crc: u16, data: &[u8]) -> u16
+{
+  data.iter().fold(crc, |crc: u16, &byte| {
+    CRC16_TABLE[((crc ^ byte as u16) & 0xFF) as usize] as u16 ^ (crc >> 8)
+  })
+}
@@ -0,0 +1,122 @@
+use crate::ext4::{file_sys::Error, inode::Flags, Extent, FileSystem, Inode};
+use std::io;
+
+impl<R> FileSystem<R>
+where
+  R: io::Read + io::Seek,
+{
+  /// Returns the extents an inode's data is stored in, regardless of whether it is mapped
+  /// through an extent tree or through the legacy block map. Inline data and fast symbolic links,
+  /// whose target takes the place of the block pointers, have no extents.
+  pub fn read_extents(&mut self, inode: &Inode) -> Result<Vec<Extent>, Error>
+  {
+    self.read_extents_with(inode, &mut |fs: &mut Self, block: u64| fs.read_block(block).map(Some))
+  }
+
+  /// Same as `read_extents`, but the extent tree and indirect blocks are read through `read`. A
+  /// block it returns `None` for is left as a hole.
+  pub(crate) fn read_extents_with<F>(&mut self, inode: &Inode, read: &mut F) -> Result<Vec<Extent>, Error>
+  where
+    F: FnMut(&mut Self, u64) -> Result<Option<Vec<u8>>, Error>,
+  {
+    if inode.flags.contains(Flags::INLINE) || inode.is_fast_symlink() {
+      Ok(Vec::new())
+    } else if inode.flags.contains(Flags::EXTENTS) {
+      self.read_extent_tree_with(inode, read)
+    } else {
+      self.read_block_map_with(inode, read)
+    }
+  }
+
+  /// Resolves the direct, indirect, double indirect and triple indirect block pointers of a
+  /// non-extent inode. Zero pointers are holes and do not show up in the returned extents, which
+  /// merge consecutive blocks that are also consecutive on disk.
+  pub fn read_block_map(&mut self, inode: &Inode) -> Result<Vec<Extent>, Error>
+  {
+    self.read_block_map_with(inode, &mut |fs: &mut Self, block: u64| fs.read_block(block).map(Some))
+  }
+
+  fn read_block_map_with<F>(&mut self, inode: &Inode, read: &mut F) -> Result<Vec<Extent>, Error>
+  where
+    F: FnMut(&mut Self, u64) -> Result<Option<Vec<u8>>, Error>,
+  {
+    let block_size: u64 = self.sb.get_block_size() as u64;
+    let per_block: u64 = block_size / 4;
+    // Nothing past the end of the file is mapped, so this also stops us from following stale
+    // pointers left behind by a truncate.
+    let count: u64 = inode.size.div_ceil(block_size).min(u32::MAX as u64);
+
+    let mut extents: Vec<Extent> = Vec::new();
+    for (logical, &block) in inode.block[..Inode::NDIR_BLOCKS].iter().enumerate() {
+      if logical as u64 >= count {
+        break;
+      }
+      push_block(&mut extents, logical as u64, block);
+    }
+
+    let mut first: u64 = Inode::NDIR_BLOCKS as u64;
+    for (level, &idx) in [Inode::IND_BLOCK, Inode::DIND_BLOCK, Inode::TIND_BLOCK]
+      .iter()
+      .enumerate()
+    {
+      let level: u32 = level as u32 + 1;
+      self.read_indirect_block(inode.block[idx], level, first, count, &mut extents, read)?;
+      first += per_block.pow(level);
+    }
+    Ok(extents)
+  }
+
+  fn read_indirect_block<F>(
+    &mut self,
+    block: u32,
+    level: u32,
+    first: u64,
+    count: u64,
+    extents: &mut Vec<Extent>,
+    read: &mut F,
+  ) -> Result<(), Error>
+  where
+    F: FnMut(&mut Self, u64) -> Result<Option<Vec<u8>>, Error>,
+  {
+    if block == 0 || first >= count {
+      return Ok(());
+    }
+    let span: u64 = (self.sb.get_block_size() as u64 / 4).pow(level - 1);
+    let pointers: Vec<u8> = match read(self, block as u64)? {
+      Some(pointers) => pointers,
+      None => return Ok(()),
+    };
+    for (i, pointer) in pointers.chunks_exact(4).enumerate() {
+      let logical: u64 = first + i as u64 * span;
+      if logical >= count {
+        break;
+      }
+      let pointer: u32 = u32::from_le_bytes([pointer[0], pointer[1], pointer[2], pointer[3]]);
+      if level == 1 {
+        push_block(extents, logical, pointer);
+      } else {
+        self.read_indirect_block(pointer, level - 1, logical, count, extents, read)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+fn push_block(extents: &mut Vec<Extent>, logical: u64, physical: u32)
+{
+  if physical == 0 {
+    return;
+  }
+  if let Some(last) = extents.last_mut() {
+    if last.block as u64 + last.len as u64 == logical && last.start + last.len as u64 == physical as u64 {
+      last.len += 1;
+      return;
+    }
+  }
+  extents.push(Extent {
+    block: logical as u32,
+    len: 1,
+    start: physical as u64,
+    unwritten: false,
+  });
+}
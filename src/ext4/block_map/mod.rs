@@ -0,0 +1 @@
+mod block_map;
@@ -0,0 +1,195 @@
+use super::Signature;
+use crate::ext4::{
+  file_sys::{Bitmap, Error},
+  group_desc::Flags as GroupFlags,
+  FileSystem,
+};
+use std::io;
+
+/// File found by the carver.
+#[derive(Debug, Clone)]
+pub struct CarvedFile
+{
+  /// Name of the format it was recognized as.
+  pub format: String,
+  /// Extension the format suggests for it.
+  pub extension: String,
+  /// Block the file starts at.
+  pub block: u64,
+  /// Whether the end of the file was found. Otherwise the file was cut short by an allocated block,
+  /// the end of the filesystem or the size limit of its format.
+  pub complete: bool,
+  pub data: Vec<u8>,
+}
+
+impl<R> FileSystem<R>
+where
+  R: io::Read + io::Seek,
+{
+  /// Carves files of the given formats out of the blocks the block bitmaps mark as free.
+  pub fn carve(&mut self, signatures: Vec<Box<dyn Signature>>) -> Carver<'_, R>
+  {
+    Carver::new(self, signatures)
+  }
+}
+
+/// Walks the free blocks of the filesystem and yields the files that start at one of them. A file
+/// is assumed to be contiguous, and carving it stops at the first block that is in use. A group
+/// whose bitmap cannot be read yields an error and the walk goes on with the next one.
+pub struct Carver<'fs, R>
+{
+  fs: &'fs mut FileSystem<R>,
+  signatures: Vec<Box<dyn Signature>>,
+  blocks_count: u64,
+  block: u64,
+  group: Option<u64>,
+  /// Block bitmap of `group`, or `None` if it was never initialized and every block past its copy
+  /// of the superblock and group descriptors is free.
+  bitmap: Option<Bitmap>,
+  /// Number of blocks at the start of `group` taken by its copy of the superblock and group
+  /// descriptors.
+  backup_blocks: u64,
+}
+
+impl<'fs, R> Carver<'fs, R>
+where
+  R: io::Read + io::Seek,
+{
+  pub fn new(fs: &'fs mut FileSystem<R>, signatures: Vec<Box<dyn Signature>>) -> Self
+  {
+    let blocks_count: u64 = fs.sb.get_blocks_count();
+    let block: u64 = fs.sb.first_data_block as u64;
+    Self {
+      fs,
+      signatures,
+      blocks_count,
+      block,
+      group: None,
+      bitmap: None,
+      backup_blocks: 0,
+    }
+  }
+
+  fn is_free(&mut self, block: u64) -> Result<bool, Error>
+  {
+    let first_data_block: u64 = self.fs.sb.first_data_block as u64;
+    let blocks_per_group: u64 = self.fs.sb.blocks_per_group as u64;
+    if block < first_data_block || block >= self.blocks_count || blocks_per_group == 0 {
+      return Ok(false);
+    }
+    let group: u64 = (block - first_data_block) / blocks_per_group;
+    if self.group != Some(group) {
+      // The group is only remembered once its bitmap was read, so a failure is not taken for a
+      // group without one.
+      self.group = None;
+      let desc = self.fs.read_group_desc(group as u32)?;
+      self.bitmap = if desc.flags.contains(GroupFlags::BLOCK_UNINIT) {
+        None
+      } else {
+        Some(self.fs.read_block_bitmap(&desc)?)
+      };
+      self.backup_blocks = self.fs.sb.get_backup_blocks(group as u32);
+      self.group = Some(group);
+    }
+
+    // With bigalloc, each bit of the bitmap stands for a whole cluster.
+    let per_bit: u64 = if self.fs.sb.feature_bigalloc() {
+      (self.fs.sb.get_cluster_size() / self.fs.sb.get_block_size()).max(1) as u64
+    } else {
+      1
+    };
+    let offset: u64 = (block - first_data_block) % blocks_per_group;
+    Ok(match &self.bitmap {
+      Some(bitmap) => !bitmap.is_set(offset / per_bit),
+      None => offset >= self.backup_blocks,
+    })
+  }
+
+  /// Extends the file starting at `block` with the free blocks that follow it until its end is
+  /// found. A block that cannot be read ends it early, and what was gathered so far is returned as
+  /// incomplete.
+  fn carve(&mut self, index: usize, block: u64, mut data: Vec<u8>) -> CarvedFile
+  {
+    let max_size: u64 = self.signatures[index].max_size();
+    let mut searched: usize = 0;
+    let mut next: u64 = block + 1;
+    let complete: bool = loop {
+      let end: Option<u64> = self.signatures[index].find_end(&data, searched);
+      searched = data.len();
+      if let Some(end) = end.filter(|&end| end <= data.len() as u64) {
+        data.truncate(end as usize);
+        break true;
+      }
+      let more: Option<Vec<u8>> = if data.len() as u64 >= max_size {
+        None
+      } else {
+        match self.is_free(next) {
+          Ok(true) => self.fs.read_block(next).ok(),
+          _ => None,
+        }
+      };
+      match more {
+        Some(more) => data.extend(more),
+        None => {
+          data.truncate(max_size.min(end.unwrap_or(u64::MAX)) as usize);
+          break false;
+        }
+      }
+      next += 1;
+    };
+
+    let signature: &dyn Signature = self.signatures[index].as_ref();
+    CarvedFile {
+      format: signature.name().to_string(),
+      extension: signature.extension(&data).to_string(),
+      block,
+      complete,
+      data,
+    }
+  }
+}
+
+impl<R> Iterator for Carver<'_, R>
+where
+  R: io::Read + io::Seek,
+{
+  type Item = Result<CarvedFile, Error>;
+
+  fn next(&mut self) -> Option<Self::Item>
+  {
+    while self.block < self.blocks_count {
+      let block: u64 = self.block;
+      self.block += 1;
+      match self.is_free(block) {
+        Ok(true) => {}
+        Ok(false) => continue,
+        Err(err) => {
+          // Skip the rest of the group rather than failing on each of its blocks.
+          let first_data_block: u64 = self.fs.sb.first_data_block as u64;
+          let blocks_per_group: u64 = self.fs.sb.blocks_per_group as u64;
+          let group: u64 = (block - first_data_block) / blocks_per_group;
+          self.block = first_data_block + (group + 1) * blocks_per_group;
+          return Some(Err(err));
+        }
+      }
+
+      let head: Vec<u8> = match self.fs.read_block(block) {
+        Ok(head) => head,
+        Err(err) => return Some(Err(err)),
+      };
+      let index: usize = match self.signatures.iter().position(|signature| signature.matches(&head)) {
+        Some(index) => index,
+        None => continue,
+      };
+      let file: CarvedFile = self.carve(index, block, head);
+      // A file cut short may hide the start of another one, so only the blocks of complete files
+      // are skipped.
+      if file.complete {
+        let block_size: u64 = self.fs.sb.get_block_size() as u64;
+        self.block = block + (file.data.len() as u64).div_ceil(block_size).max(1);
+      }
+      return Some(Ok(file));
+    }
+    None
+  }
+}
@@ -0,0 +1,377 @@
+use super::Signature;
+use std::convert::TryInto;
+
+/// JPEG image. The end is the first EOI marker past the first scan, since markers inside the
+/// entropy-coded data are always escaped and thumbnails live in segments that are skipped.
+pub struct Jpeg;
+
+impl Signature for Jpeg
+{
+  fn name(&self) -> &str
+  {
+    "jpeg"
+  }
+
+  fn extension(&self, _data: &[u8]) -> &str
+  {
+    "jpg"
+  }
+
+  fn matches(&self, block: &[u8]) -> bool
+  {
+    block.starts_with(b"\xFF\xD8\xFF")
+  }
+
+  fn find_end(&self, data: &[u8], searched: usize) -> Option<u64>
+  {
+    // Walk the segments in front of the first scan, which all carry their length.
+    let mut pos: usize = 2;
+    loop {
+      let marker: u8 = *data.get(pos + 1)?;
+      if data[pos] != 0xFF {
+        return Some(pos as u64);
+      }
+      match marker {
+        0xFF => pos += 1,
+        0xD9 => return Some(pos as u64 + 2),
+        0x01 | 0xD0..=0xD7 => pos += 2,
+        _ => {
+          let len: usize = be16(data, pos + 2)? as usize;
+          pos += 2 + len;
+          if marker == 0xDA {
+            break;
+          }
+        }
+      }
+    }
+    let from: usize = pos.max(searched.saturating_sub(1));
+    find(data.get(from..)?, b"\xFF\xD9").map(|end| (from + end + 2) as u64)
+  }
+}
+
+/// PNG image, which ends with its IEND chunk.
+pub struct Png;
+
+impl Signature for Png
+{
+  fn name(&self) -> &str
+  {
+    "png"
+  }
+
+  fn extension(&self, _data: &[u8]) -> &str
+  {
+    "png"
+  }
+
+  fn matches(&self, block: &[u8]) -> bool
+  {
+    block.starts_with(b"\x89PNG\r\n\x1A\n")
+  }
+
+  fn find_end(&self, data: &[u8], _searched: usize) -> Option<u64>
+  {
+    let mut pos: u64 = 8;
+    loop {
+      // Length, type, data and CRC.
+      let len: u64 = be32(data, pos as usize)? as u64;
+      let end: u64 = pos + 12 + len;
+      if data.get(pos as usize + 4..pos as usize + 8)? == b"IEND" {
+        return Some(end);
+      }
+      pos = end;
+    }
+  }
+}
+
+/// PDF document. Documents that were updated incrementally have several end-of-file markers, of
+/// which only the first can be told apart from the data that follows.
+pub struct Pdf;
+
+impl Signature for Pdf
+{
+  fn name(&self) -> &str
+  {
+    "pdf"
+  }
+
+  fn extension(&self, _data: &[u8]) -> &str
+  {
+    "pdf"
+  }
+
+  fn matches(&self, block: &[u8]) -> bool
+  {
+    block.starts_with(b"%PDF-")
+  }
+
+  fn find_end(&self, data: &[u8], searched: usize) -> Option<u64>
+  {
+    let from: usize = searched.saturating_sub(4);
+    let mut end: usize = from + find(&data[from..], b"%%EOF")? + 5;
+    // The marker may still be followed by the end of its line.
+    for &eol in b"\r\n" {
+      if data.get(end) == Some(&eol) {
+        end += 1;
+      }
+    }
+    Some(end as u64)
+  }
+}
+
+/// ZIP archive, along with the Office Open XML documents built on top of it. The end is the end of
+/// central directory record, which may be followed by a comment.
+pub struct Zip;
+
+impl Zip
+{
+  const EOCD_SIZE: usize = 22;
+}
+
+impl Signature for Zip
+{
+  fn name(&self) -> &str
+  {
+    "zip"
+  }
+
+  fn extension(&self, data: &[u8]) -> &str
+  {
+    if find(data, b"[Content_Types].xml").is_none() {
+      "zip"
+    } else if find(data, b"word/").is_some() {
+      "docx"
+    } else if find(data, b"xl/").is_some() {
+      "xlsx"
+    } else if find(data, b"ppt/").is_some() {
+      "pptx"
+    } else {
+      "zip"
+    }
+  }
+
+  fn matches(&self, block: &[u8]) -> bool
+  {
+    block.starts_with(b"PK\x03\x04")
+  }
+
+  fn find_end(&self, data: &[u8], searched: usize) -> Option<u64>
+  {
+    // The record is only looked at once it was read whole, so the search has to start early enough
+    // to see it again.
+    let from: usize = searched.saturating_sub(Self::EOCD_SIZE);
+    let pos: usize = from + find(&data[from..], b"PK\x05\x06")?;
+    let comment_len: u64 = le16(data, pos + 20)? as u64;
+    Some((pos + Self::EOCD_SIZE) as u64 + comment_len)
+  }
+}
+
+/// SQLite database, whose size is the page size times the page count from its header.
+pub struct Sqlite;
+
+impl Signature for Sqlite
+{
+  fn name(&self) -> &str
+  {
+    "sqlite"
+  }
+
+  fn extension(&self, _data: &[u8]) -> &str
+  {
+    "sqlite"
+  }
+
+  fn matches(&self, block: &[u8]) -> bool
+  {
+    block.starts_with(b"SQLite format 3\x00")
+  }
+
+  fn find_end(&self, data: &[u8], _searched: usize) -> Option<u64>
+  {
+    let page_size: u64 = match be16(data, 16)? {
+      1 => 65536,
+      size => size as u64,
+    };
+    // The page count is only valid if it was written by the same change as the change counter.
+    if be32(data, 24)? != be32(data, 92)? {
+      return None;
+    }
+    Some(page_size * be32(data, 28)? as u64)
+  }
+}
+
+/// Gzip stream. Nothing short of inflating it tells where it ends, so it spans the whole run of
+/// free blocks it starts.
+pub struct Gzip;
+
+impl Signature for Gzip
+{
+  fn name(&self) -> &str
+  {
+    "gzip"
+  }
+
+  fn extension(&self, _data: &[u8]) -> &str
+  {
+    "gz"
+  }
+
+  fn matches(&self, block: &[u8]) -> bool
+  {
+    // Deflate is the only compression method there is.
+    block.starts_with(b"\x1F\x8B\x08")
+  }
+
+  fn find_end(&self, _data: &[u8], _searched: usize) -> Option<u64>
+  {
+    None
+  }
+}
+
+/// ELF executable or object. The section header table usually comes last; the segments are
+/// taken into account for files that were stripped of it.
+pub struct Elf;
+
+impl Signature for Elf
+{
+  fn name(&self) -> &str
+  {
+    "elf"
+  }
+
+  fn extension(&self, _data: &[u8]) -> &str
+  {
+    "elf"
+  }
+
+  fn matches(&self, block: &[u8]) -> bool
+  {
+    block.starts_with(b"\x7FELF")
+      && matches!(block.get(4), Some(1) | Some(2))
+      && matches!(block.get(5), Some(1) | Some(2))
+  }
+
+  fn find_end(&self, data: &[u8], _searched: usize) -> Option<u64>
+  {
+    let wide: bool = data[4] == 2;
+    let big: bool = data[5] == 2;
+    let half = |at: usize| -> Option<u64> {
+      let bytes: [u8; 2] = data.get(at..at.checked_add(2)?)?.try_into().ok()?;
+      Some(if big {
+        u16::from_be_bytes(bytes)
+      } else {
+        u16::from_le_bytes(bytes)
+      } as u64)
+    };
+    let word = |at: usize| -> Option<u64> {
+      let bytes: [u8; 4] = data.get(at..at.checked_add(4)?)?.try_into().ok()?;
+      Some(if big {
+        u32::from_be_bytes(bytes)
+      } else {
+        u32::from_le_bytes(bytes)
+      } as u64)
+    };
+    let addr = |at: usize| -> Option<u64> {
+      if wide {
+        let bytes: [u8; 8] = data.get(at..at.checked_add(8)?)?.try_into().ok()?;
+        Some(if big {
+          u64::from_be_bytes(bytes)
+        } else {
+          u64::from_le_bytes(bytes)
+        })
+      } else {
+        word(at)
+      }
+    };
+
+    // Offsets of e_phoff, e_shoff, e_phentsize, e_phnum, e_shentsize and e_shnum.
+    let (e_phoff, e_shoff, e_phentsize) = if wide { (32, 40, 54) } else { (28, 32, 42) };
+    let phoff: u64 = addr(e_phoff)?;
+    let shoff: u64 = addr(e_shoff)?;
+    let phentsize: u64 = half(e_phentsize)?;
+    let phnum: u64 = half(e_phentsize + 2)?;
+    let shentsize: u64 = half(e_phentsize + 4)?;
+    let shnum: u64 = half(e_phentsize + 6)?;
+
+    // Garbage headers must not overflow, since only the carver's size limit stops them.
+    let mut end: u64 = phoff
+      .saturating_add(phentsize * phnum)
+      .max(shoff.saturating_add(shentsize * shnum));
+    // Offsets of p_offset and p_filesz within a program header.
+    let (p_offset, p_filesz) = if wide { (8, 32) } else { (4, 16) };
+    for i in 0..phnum {
+      let header: usize = phoff.saturating_add(i * phentsize) as usize;
+      end = end.max(addr(header.saturating_add(p_offset))?.saturating_add(addr(header.saturating_add(p_filesz))?));
+    }
+    Some(end)
+  }
+}
+
+/// MP4 and other ISO base media files, which are a sequence of boxes starting with `ftyp`. The
+/// file ends where something that is not a box header follows.
+pub struct Mp4;
+
+impl Signature for Mp4
+{
+  fn name(&self) -> &str
+  {
+    "mp4"
+  }
+
+  fn extension(&self, _data: &[u8]) -> &str
+  {
+    "mp4"
+  }
+
+  fn matches(&self, block: &[u8]) -> bool
+  {
+    block.get(4..8) == Some(b"ftyp") && be32(block, 0).is_some_and(|size| (8..=block.len() as u32).contains(&size))
+  }
+
+  fn find_end(&self, data: &[u8], _searched: usize) -> Option<u64>
+  {
+    let mut pos: u64 = 0;
+    loop {
+      let start: usize = pos.try_into().ok()?;
+      let header: &[u8] = data.get(start..start.checked_add(8)?)?;
+      if !header[4..].iter().all(|b| (b' '..=b'~').contains(b)) {
+        return Some(pos);
+      }
+      let size: u64 = match be32(header, 0)? {
+        // The last box may extend to the end of the file, which is then unknown.
+        0 => return None,
+        1 => u64::from_be_bytes(data.get(start + 8..start + 16)?.try_into().ok()?),
+        size => size as u64,
+      };
+      if size < 8 {
+        return Some(pos);
+      }
+      pos = pos.saturating_add(size);
+    }
+  }
+
+  fn max_size(&self) -> u64
+  {
+    256 << 20
+  }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize>
+{
+  haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+fn be16(data: &[u8], at: usize) -> Option<u16>
+{
+  Some(u16::from_be_bytes(data.get(at..at.checked_add(2)?)?.try_into().ok()?))
+}
+
+fn le16(data: &[u8], at: usize) -> Option<u16>
+{
+  Some(u16::from_le_bytes(data.get(at..at.checked_add(2)?)?.try_into().ok()?))
+}
+
+fn be32(data: &[u8], at: usize) -> Option<u32>
+{
+  Some(u32::from_be_bytes(data.get(at..at.checked_add(4)?)?.try_into().ok()?))
+}
@@ -0,0 +1,7 @@
+mod carver;
+mod formats;
+mod signature;
+
+pub use carver::{CarvedFile, Carver};
+pub use formats::{Elf, Gzip, Jpeg, Mp4, Pdf, Png, Sqlite, Zip};
+pub use signature::{default_signatures, Signature};
@@ -0,0 +1,42 @@
+use super::formats::{Elf, Gzip, Jpeg, Mp4, Pdf, Png, Sqlite, Zip};
+
+/// File format the carver can recognize. Only the start of blocks is ever matched against, since
+/// ext4 always starts a file on a block boundary.
+pub trait Signature
+{
+  /// Short name of the format.
+  fn name(&self) -> &str;
+
+  /// Extension to give a carved file, which may depend on its contents.
+  fn extension(&self, data: &[u8]) -> &str;
+
+  /// Whether a file of this format starts at the beginning of `block`.
+  fn matches(&self, block: &[u8]) -> bool;
+
+  /// Looks for the end of a file whose first bytes are in `data`. The first `searched` bytes were
+  /// already looked at by a previous call, so footers only need to be searched for past them.
+  /// Returns the size of the file, which can lie past the end of `data` when the format records
+  /// it up front, or `None` if the end has not been found yet.
+  fn find_end(&self, data: &[u8], searched: usize) -> Option<u64>;
+
+  /// Size at which the carver stops looking for the end of a file.
+  fn max_size(&self) -> u64
+  {
+    64 << 20
+  }
+}
+
+/// Returns the signatures of all the built-in formats.
+pub fn default_signatures() -> Vec<Box<dyn Signature>>
+{
+  vec![
+    Box::new(Jpeg),
+    Box::new(Png),
+    Box::new(Pdf),
+    Box::new(Zip),
+    Box::new(Sqlite),
+    Box::new(Gzip),
+    Box::new(Elf),
+    Box::new(Mp4),
+  ]
+}
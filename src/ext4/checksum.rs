@@ -0,0 +1,24 @@
+/// Reason a block of metadata fails its checksum.
+#[derive(Debug)]
+pub enum ChecksumError
+{
+  /// The block has no room for the checksum it should have.
+  Missing,
+  /// The checksum stored, then the one computed. Checksums only 16 bits wide are widened.
+  Mismatch(u32, u32),
+}
+
+impl std::fmt::Display for ChecksumError
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    match self {
+      Self::Missing => write!(f, "The block has no room for a checksum."),
+      Self::Mismatch(expected, calculated) => write!(
+        f,
+        "Expected checksum was {:#010X} but computed {:#010X}.",
+        expected, calculated
+      ),
+    }
+  }
+}
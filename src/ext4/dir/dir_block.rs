@@ -0,0 +1,117 @@
+use super::{DirEntry, DxCountLimitRaw, DxEntryRaw, DxRoot, DxRootInfoRaw};
+use crate::{crc, ext4::ChecksumError};
+
+/// Block of a directory, which ends with a checksum when metadata_csum is enabled. Leaves keep it
+/// in a fake directory entry at the end, and htree interior nodes right past the room for their
+/// entries.
+#[derive(Debug, Clone)]
+pub struct DirBlock
+{
+  /// Block of the directory, counted from its start.
+  pub index: u32,
+  /// Whether the block is an htree interior node rather than a leaf.
+  pub htree: bool,
+  /// Checksum stored in the block, or `None` if it has no room for one.
+  pub checksum: Option<u32>,
+  /// Checksum computed over the block when it was read, if the filesystem has metadata_csum. It
+  /// covers the whole block if there is no room for a checksum.
+  pub calculated_checksum: Option<u32>,
+}
+
+impl DirBlock
+{
+  /// Length of the tail of an htree interior node, a reserved field and the checksum.
+  pub const DX_TAIL_LEN: usize = 8;
+
+  /// Finds the checksum of a directory block and computes the one it should have with the seed of
+  /// the directory inode, if the filesystem has metadata_csum. Blocks of hashed directories
+  /// without a leaf tail are taken for htree interior nodes.
+  pub fn new(block: &[u8], index: u32, seed: Option<u32>, hashed: bool) -> Self
+  {
+    let tail: usize = block.len().saturating_sub(DirEntry::TAIL_REC_LEN as usize);
+    if DirEntry::is_tail(&block[tail..]) {
+      return Self {
+        index,
+        htree: false,
+        checksum: Some(u32::from_le_bytes([
+          block[tail + 8],
+          block[tail + 9],
+          block[tail + 10],
+          block[tail + 11],
+        ])),
+        calculated_checksum: seed.map(|seed| crc::crc32c(seed, &block[..tail])),
+      };
+    }
+
+    let count_offset: Option<usize> = hashed.then(|| Self::get_count_offset(block)).flatten();
+    if let Some(count_offset) = count_offset {
+      let limit: usize = u16::from_le_bytes([block[count_offset], block[count_offset + 1]]) as usize;
+      let count: usize = u16::from_le_bytes([block[count_offset + 2], block[count_offset + 3]]) as usize;
+      // The checksum covers the entries in use, then the tail past the room for all of them with
+      // its checksum zeroed.
+      let tail: usize = count_offset + limit * DxEntryRaw::WIDTH;
+      let size: usize = count_offset + count * DxEntryRaw::WIDTH;
+      if tail + Self::DX_TAIL_LEN <= block.len() && size <= tail {
+        return Self {
+          index,
+          htree: true,
+          checksum: Some(u32::from_le_bytes([
+            block[tail + 4],
+            block[tail + 5],
+            block[tail + 6],
+            block[tail + 7],
+          ])),
+          calculated_checksum: seed.map(|seed| {
+            let csum: u32 = crc::crc32c(seed, &block[..size]);
+            crc::crc32c(crc::crc32c(csum, &block[tail..tail + 4]), &[0; 4])
+          }),
+        };
+      }
+    }
+    Self {
+      index,
+      htree: count_offset.is_some(),
+      checksum: None,
+      calculated_checksum: seed.map(|seed| crc::crc32c(seed, block)),
+    }
+  }
+
+  /// Returns the offset of the limit and count of the entries of an htree node, which sit behind
+  /// an empty entry spanning the block, or behind the '.' and '..' entries and the tree
+  /// information in the root.
+  fn get_count_offset(block: &[u8]) -> Option<usize>
+  {
+    let rec_len = |offset: usize| {
+      block
+        .get(offset + 4..offset + 6)
+        .map(|rec_len| u16::from_le_bytes([rec_len[0], rec_len[1]]) as usize)
+    };
+    let root: usize = DxRoot::INFO_OFFSET + DxRootInfoRaw::WIDTH;
+    let count_offset: usize = if rec_len(0)? == block.len() {
+      DirEntry::RAW_WIDTH
+    } else if rec_len(0)? == DxRoot::DOTDOT_OFFSET
+      && rec_len(DxRoot::DOTDOT_OFFSET)? == block.len() - DxRoot::DOTDOT_OFFSET
+      && block.get(DxRoot::INFO_OFFSET..DxRoot::INFO_OFFSET + 4)? == [0; 4]
+      && *block.get(DxRoot::INFO_OFFSET + 5)? as usize == DxRootInfoRaw::WIDTH
+    {
+      root
+    } else {
+      return None;
+    };
+    (count_offset + DxCountLimitRaw::WIDTH <= block.len()).then_some(count_offset)
+  }
+
+  /// Compares the checksum with the one computed when the block was read. A mismatch means the
+  /// block does not belong to the directory anymore, or never held its entries.
+  pub fn check_checksum(&self) -> Option<ChecksumError>
+  {
+    match (self.checksum, self.calculated_checksum) {
+      (_, None) => None,
+      (None, Some(_)) => Some(ChecksumError::Missing),
+      (Some(checksum), Some(calculated)) if checksum != calculated => {
+        Some(ChecksumError::Mismatch(checksum, calculated))
+      }
+      _ => None,
+    }
+  }
+}
@@ -0,0 +1,166 @@
+use super::DirEntryRaw;
+use crate::ext4::{inode::FileType, superblock::HashVersion};
+use std::borrow::Cow;
+use std::io;
+
+#[derive(Debug, Clone)]
+pub struct DirEntry
+{
+  /// Number of the inode that this directory entry points to.
+  pub inode: u32,
+  /// Length of this directory entry.
+  pub rec_len: u32,
+  /// Length of the file name.
+  pub name_len: u16,
+  /// File type, if the filetype feature is set.
+  pub file_type: Option<FileType>,
+  /// File name, as stored on disk.
+  pub name: Vec<u8>,
+}
+
+impl DirEntry
+{
+  pub const RAW_WIDTH: usize = DirEntryRaw::WIDTH;
+
+  /// File type code of the fake directory entry that holds the leaf block checksum when
+  /// metadata_csum is enabled.
+  pub const TAIL_FILE_TYPE: u8 = 0xDE;
+  /// Length of the checksum tail at the end of a directory leaf block.
+  pub const TAIL_REC_LEN: u32 = 12;
+
+  /// Reads a directory entry. `filetype` tells whether the filetype feature is set, in which case
+  /// the upper byte of the name length holds the file type instead. `block_size` is needed to
+  /// decode the record length of 64KiB blocks.
+  pub fn new<R>(inner: &mut R, filetype: bool, block_size: u32) -> Result<Self, Error>
+  where
+    R: io::Read,
+  {
+    let mut block: [u8; Self::RAW_WIDTH] = [0; Self::RAW_WIDTH];
+    inner.read_exact(&mut block)?;
+    let raw = DirEntryRaw::from(&block);
+    let (name_len, file_type): (u16, Option<FileType>) = if filetype {
+      (raw.name_len as u16, Some(FileType::from_code(raw.file_type)))
+    } else {
+      (u16::from_le_bytes([raw.name_len, raw.file_type]), None)
+    };
+    // A record length of 65536 does not fit in 16 bits and is stored as either 0 or 65535.
+    let rec_len: u32 = if block_size >= 65536 && (raw.rec_len == 0 || raw.rec_len == u16::MAX) {
+      block_size
+    } else {
+      raw.rec_len as u32
+    };
+    if rec_len < Self::RAW_WIDTH as u32
+      || rec_len % 4 != 0
+      || (name_len as u32) > rec_len - Self::RAW_WIDTH as u32
+    {
+      return Err(Error::RecLen(rec_len, name_len));
+    }
+    let mut name: Vec<u8> = vec![0; name_len as usize];
+    inner.read_exact(&mut name)?;
+    Ok(Self {
+      inode: raw.inode,
+      rec_len,
+      name_len,
+      file_type,
+      name,
+    })
+  }
+
+  /// Reads the next entry of a leaf block, starting at `offset` and moving it past the entry.
+  /// Unused entries and the checksum tail are skipped, and `None` is returned once the end of the
+  /// block is reached. After a malformed entry, `offset` is moved to the end of the block.
+  pub(crate) fn next_in_block(
+    block: &[u8],
+    offset: &mut usize,
+    filetype: bool,
+    block_size: u32,
+  ) -> Option<Result<Self, Error>>
+  {
+    while *offset < block.len() {
+      let rest: &[u8] = &block[*offset..];
+      if Self::is_tail(rest) {
+        *offset += Self::TAIL_REC_LEN as usize;
+        continue;
+      }
+      match Self::new(&mut &rest[..], filetype, block_size) {
+        Ok(entry) if entry.rec_len as usize > rest.len() => {
+          *offset = block.len();
+          return Some(Err(Error::RecLen(entry.rec_len, entry.name_len)));
+        }
+        Ok(entry) => {
+          *offset += entry.rec_len as usize;
+          if entry.inode != 0 {
+            return Some(Ok(entry));
+          }
+        }
+        Err(err) => {
+          *offset = block.len();
+          return Some(Err(err));
+        }
+      }
+    }
+    None
+  }
+
+  /// The file name, with invalid UTF-8 sequences replaced.
+  pub fn get_name(&self) -> Cow<'_, str>
+  {
+    String::from_utf8_lossy(&self.name)
+  }
+
+  /// Whether the bytes start with the fake directory entry that holds the leaf block checksum
+  /// when metadata_csum is enabled.
+  pub fn is_tail(block: &[u8]) -> bool
+  {
+    block.len() >= Self::TAIL_REC_LEN as usize
+      && block[..4] == [0; 4]
+      && u16::from_le_bytes([block[4], block[5]]) as u32 == Self::TAIL_REC_LEN
+      && block[6] == 0
+      && block[7] == Self::TAIL_FILE_TYPE
+  }
+}
+
+#[derive(Debug)]
+pub enum Error
+{
+  IO(io::Error),
+  RecLen(u32, u16),
+  Hole(u32),
+  DxRootInfo,
+  DxNode,
+  DxCount(u16, u16),
+  DxLevels(u8),
+  DxHashVersion(HashVersion),
+}
+
+impl From<io::Error> for Error
+{
+  fn from(error: io::Error) -> Self
+  {
+    Self::IO(error)
+  }
+}
+
+impl std::fmt::Display for Error
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    write!(
+      f,
+      "Directory entry error: {}",
+      match self {
+        Self::IO(error) => format!("An IO error occurred: {}", error),
+        Self::RecLen(rec_len, name_len) => format!(
+          "Record length {} is invalid for a name length of {}.",
+          rec_len, name_len
+        ),
+        Self::Hole(block) => format!("Directory block {} is not mapped.", block),
+        Self::DxRootInfo => String::from("Htree root information is corrupted."),
+        Self::DxNode => String::from("Htree node does not start with an empty directory entry."),
+        Self::DxCount(count, limit) => format!("Htree node claims {} entries with a limit of {}.", count, limit),
+        Self::DxLevels(levels) => format!("Htree has too many indirect levels ({}).", levels),
+        Self::DxHashVersion(version) => format!("Htree uses an unsupported hash: {}.", version),
+      }
+    )
+  }
+}
@@ -0,0 +1,181 @@
+use crate::ext4::superblock::HashVersion;
+
+/// Largest hash value a 32-bit htree hash may take, since the lowest bit is reserved.
+const HTREE_EOF_32BIT: u32 = 0x7FFF_FFFF;
+
+const TEA_DELTA: u32 = 0x9E37_79B9;
+
+const MD4_K1: u32 = 0;
+const MD4_K2: u32 = 0o13240474631;
+const MD4_K3: u32 = 0o15666365641;
+
+/// Computes the major and minor htree hash of a file name. `seed` is the filesystem's hash seed
+/// as four native words; an all-zero seed selects the default one. Unknown hash versions have no
+/// hash.
+pub fn dx_hash(name: &[u8], version: &HashVersion, seed: &[u32; 4]) -> Option<(u32, u32)>
+{
+  let mut buf: [u32; 4] = if seed.iter().any(|&word| word != 0) {
+    *seed
+  } else {
+    [0x6745_2301, 0xEFCD_AB89, 0x98BA_DCFE, 0x1032_5476]
+  };
+
+  let (hash, minor_hash): (u32, u32) = match version {
+    HashVersion::Legacy => (dx_hack_hash(name, true), 0),
+    HashVersion::LegacyUnsigned => (dx_hack_hash(name, false), 0),
+    HashVersion::HalfMD4 | HashVersion::HalfMD4Unsigned => {
+      let signed: bool = *version == HashVersion::HalfMD4;
+      let mut input: [u32; 8] = [0; 8];
+      for chunk in chunks(name, 32) {
+        str2hashbuf(chunk, signed, &mut input);
+        half_md4_transform(&mut buf, &input);
+      }
+      (buf[1], buf[2])
+    }
+    HashVersion::Tea | HashVersion::TeaUnsigned => {
+      let signed: bool = *version == HashVersion::Tea;
+      let mut input: [u32; 4] = [0; 4];
+      for chunk in chunks(name, 16) {
+        str2hashbuf(chunk, signed, &mut input);
+        tea_transform(&mut buf, &input);
+      }
+      (buf[0], buf[1])
+    }
+    HashVersion::Unknown(_) => return None,
+  };
+
+  let hash: u32 = hash & !1;
+  if hash == HTREE_EOF_32BIT << 1 {
+    Some(((HTREE_EOF_32BIT - 1) << 1, minor_hash))
+  } else {
+    Some((hash, minor_hash))
+  }
+}
+
+/// Splits the name into the pieces fed to the hash transforms. Each piece starts at a multiple of
+/// `len` and runs to the end of the name, since `str2hashbuf` pads with the remaining length.
+fn chunks(name: &[u8], len: usize) -> impl Iterator<Item = &[u8]>
+{
+  (0..name.len())
+    .step_by(len)
+    .map(move |start| &name[start.min(name.len())..])
+}
+
+/// Characters are sign extended in the signed variants, which is what the kernel ends up doing on
+/// architectures where `char` is signed.
+#[inline(always)]
+fn char_value(c: u8, signed: bool) -> u32
+{
+  if signed {
+    c as i8 as i32 as u32
+  } else {
+    c as u32
+  }
+}
+
+fn dx_hack_hash(name: &[u8], signed: bool) -> u32
+{
+  let mut hash0: u32 = 0x12A3_FE2D;
+  let mut hash1: u32 = 0x37AB_E8F9;
+  for &c in name {
+    let mut hash: u32 = hash1.wrapping_add(hash0 ^ char_value(c, signed).wrapping_mul(7_152_373));
+    if hash & 0x8000_0000 != 0 {
+      hash = hash.wrapping_sub(0x7FFF_FFFF);
+    }
+    hash1 = hash0;
+    hash0 = hash;
+  }
+  hash0 << 1
+}
+
+fn str2hashbuf(msg: &[u8], signed: bool, buf: &mut [u32])
+{
+  let mut pad: u32 = msg.len() as u32 | ((msg.len() as u32) << 8);
+  pad |= pad << 16;
+
+  let len: usize = msg.len().min(buf.len() * 4);
+  let mut val: u32 = pad;
+  let mut idx: usize = 0;
+  for (i, &c) in msg[..len].iter().enumerate() {
+    val = char_value(c, signed).wrapping_add(val << 8);
+    if i % 4 == 3 {
+      buf[idx] = val;
+      idx += 1;
+      val = pad;
+    }
+  }
+  if idx < buf.len() {
+    buf[idx] = val;
+    idx += 1;
+  }
+  buf[idx..].iter_mut().for_each(|word| *word = pad);
+}
+
+fn tea_transform(buf: &mut [u32; 4], input: &[u32; 4])
+{
+  let mut sum: u32 = 0;
+  let (mut b0, mut b1) = (buf[0], buf[1]);
+  let (a, b, c, d) = (input[0], input[1], input[2], input[3]);
+  for _ in 0..16 {
+    sum = sum.wrapping_add(TEA_DELTA);
+    b0 = b0.wrapping_add(((b1 << 4).wrapping_add(a)) ^ (b1.wrapping_add(sum)) ^ ((b1 >> 5).wrapping_add(b)));
+    b1 = b1.wrapping_add(((b0 << 4).wrapping_add(c)) ^ (b0.wrapping_add(sum)) ^ ((b0 >> 5).wrapping_add(d)));
+  }
+  buf[0] = buf[0].wrapping_add(b0);
+  buf[1] = buf[1].wrapping_add(b1);
+}
+
+fn half_md4_transform(buf: &mut [u32; 4], input: &[u32; 8])
+{
+  fn f(x: u32, y: u32, z: u32) -> u32
+  {
+    z ^ (x & (y ^ z))
+  }
+  fn g(x: u32, y: u32, z: u32) -> u32
+  {
+    (x & y).wrapping_add((x ^ y) & z)
+  }
+  fn h(x: u32, y: u32, z: u32) -> u32
+  {
+    x ^ y ^ z
+  }
+  fn round(func: fn(u32, u32, u32) -> u32, a: u32, b: u32, c: u32, d: u32, x: u32, s: u32) -> u32
+  {
+    a.wrapping_add(func(b, c, d)).wrapping_add(x).rotate_left(s)
+  }
+
+  let (mut a, mut b, mut c, mut d) = (buf[0], buf[1], buf[2], buf[3]);
+  let k = |i: usize, k: u32| input[i].wrapping_add(k);
+
+  a = round(f, a, b, c, d, k(0, MD4_K1), 3);
+  d = round(f, d, a, b, c, k(1, MD4_K1), 7);
+  c = round(f, c, d, a, b, k(2, MD4_K1), 11);
+  b = round(f, b, c, d, a, k(3, MD4_K1), 19);
+  a = round(f, a, b, c, d, k(4, MD4_K1), 3);
+  d = round(f, d, a, b, c, k(5, MD4_K1), 7);
+  c = round(f, c, d, a, b, k(6, MD4_K1), 11);
+  b = round(f, b, c, d, a, k(7, MD4_K1), 19);
+
+  a = round(g, a, b, c, d, k(1, MD4_K2), 3);
+  d = round(g, d, a, b, c, k(3, MD4_K2), 5);
+  c = round(g, c, d, a, b, k(5, MD4_K2), 9);
+  b = round(g, b, c, d, a, k(7, MD4_K2), 13);
+  a = round(g, a, b, c, d, k(0, MD4_K2), 3);
+  d = round(g, d, a, b, c, k(2, MD4_K2), 5);
+  c = round(g, c, d, a, b, k(4, MD4_K2), 9);
+  b = round(g, b, c, d, a, k(6, MD4_K2), 13);
+
+  a = round(h, a, b, c, d, k(3, MD4_K3), 3);
+  d = round(h, d, a, b, c, k(7, MD4_K3), 9);
+  c = round(h, c, d, a, b, k(2, MD4_K3), 11);
+  b = round(h, b, c, d, a, k(6, MD4_K3), 15);
+  a = round(h, a, b, c, d, k(1, MD4_K3), 3);
+  d = round(h, d, a, b, c, k(5, MD4_K3), 9);
+  c = round(h, c, d, a, b, k(0, MD4_K3), 11);
+  b = round(h, b, c, d, a, k(4, MD4_K3), 15);
+
+  buf[0] = buf[0].wrapping_add(a);
+  buf[1] = buf[1].wrapping_add(b);
+  buf[2] = buf[2].wrapping_add(c);
+  buf[3] = buf[3].wrapping_add(d);
+}
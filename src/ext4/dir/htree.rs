@@ -0,0 +1,122 @@
+use super::{DirEntry, DxCountLimitRaw, DxEntryRaw, DxRootInfoRaw, Error};
+use crate::ext4::superblock::HashVersion;
+use std::io;
+
+/// Entry of an htree interior node. It maps the hashes from `hash` up to the next entry's hash to
+/// a block of the directory.
+#[derive(Debug, Copy, Clone)]
+pub struct DxEntry
+{
+  /// Hash code.
+  pub hash: u32,
+  /// Block number (within the directory file, not filesystem blocks) of the
+  /// next node in the htree.
+  pub block: u32,
+}
+
+/// Root of an htree, which lives in the first block of a hashed directory behind the '.' and '..'
+/// entries.
+#[derive(Debug)]
+pub struct DxRoot
+{
+  /// The '.' entry.
+  pub dot: DirEntry,
+  /// The '..' entry, whose record length covers the rest of the block.
+  pub dotdot: DirEntry,
+  /// Hash type.
+  pub hash_version: HashVersion,
+  /// Length of the tree information, 0x8.
+  pub info_length: u8,
+  /// Depth of the htree. Cannot be larger than 3 if the INCOMPAT_LARGEDIR
+  /// feature is set; cannot be larger than 2 otherwise.
+  pub indirect_levels: u8,
+  /// Maximum number of entries that fit in the root.
+  pub limit: u16,
+  /// Entries of the root, ordered by hash. The first one covers hash 0 onward.
+  pub entries: Vec<DxEntry>,
+}
+
+impl DxRoot
+{
+  /// Offset of the tree information, right past the '.' and '..' entries.
+  pub const INFO_OFFSET: usize = 24;
+  /// Offset of the '..' entry.
+  pub(crate) const DOTDOT_OFFSET: usize = 12;
+
+  pub fn new(block: &[u8], filetype: bool, block_size: u32) -> Result<Self, Error>
+  {
+    if block.len() < Self::INFO_OFFSET + DxRootInfoRaw::WIDTH {
+      return Err(Error::IO(io::ErrorKind::UnexpectedEof.into()));
+    }
+    let dot = DirEntry::new(&mut &block[..], filetype, block_size)?;
+    let dotdot = DirEntry::new(&mut &block[Self::DOTDOT_OFFSET..], filetype, block_size)?;
+
+    let mut info: [u8; DxRootInfoRaw::WIDTH] = [0; DxRootInfoRaw::WIDTH];
+    info.copy_from_slice(&block[Self::INFO_OFFSET..Self::INFO_OFFSET + DxRootInfoRaw::WIDTH]);
+    let info = DxRootInfoRaw::from(&info);
+    if info.reserved_zero != 0 || info.info_length as usize != DxRootInfoRaw::WIDTH {
+      return Err(Error::DxRootInfo);
+    }
+
+    let (limit, entries) = read_dx_entries(&block[Self::INFO_OFFSET + info.info_length as usize..])?;
+    Ok(Self {
+      dot,
+      dotdot,
+      hash_version: HashVersion::from_raw(info.hash_version),
+      info_length: info.info_length,
+      indirect_levels: info.indirect_levels,
+      limit,
+      entries,
+    })
+  }
+}
+
+/// Interior node of an htree. It hides behind an empty directory entry that spans the whole
+/// block, so that code unaware of htrees sees an empty leaf.
+#[derive(Debug)]
+pub struct DxNode
+{
+  /// Maximum number of entries that fit in the node.
+  pub limit: u16,
+  /// Entries of the node, ordered by hash.
+  pub entries: Vec<DxEntry>,
+}
+
+impl DxNode
+{
+  pub fn new(block: &[u8]) -> Result<Self, Error>
+  {
+    if block.len() < DirEntry::RAW_WIDTH || block[..4] != [0; 4] {
+      return Err(Error::DxNode);
+    }
+    let (limit, entries) = read_dx_entries(&block[DirEntry::RAW_WIDTH..])?;
+    Ok(Self { limit, entries })
+  }
+}
+
+fn read_dx_entries(mut inner: &[u8]) -> Result<(u16, Vec<DxEntry>), Error>
+{
+  let mut block: [u8; DxCountLimitRaw::WIDTH] = [0; DxCountLimitRaw::WIDTH];
+  io::Read::read_exact(&mut inner, &mut block)?;
+  let count_limit = DxCountLimitRaw::from(&block);
+  let count: usize = count_limit.count as usize;
+  if count == 0 || count > count_limit.limit as usize || (count - 1) * DxEntryRaw::WIDTH > inner.len() {
+    return Err(Error::DxCount(count_limit.count, count_limit.limit));
+  }
+
+  let mut entries: Vec<DxEntry> = Vec::with_capacity(count);
+  entries.push(DxEntry {
+    hash: 0,
+    block: count_limit.block,
+  });
+  for _ in 1..count {
+    let mut block: [u8; DxEntryRaw::WIDTH] = [0; DxEntryRaw::WIDTH];
+    io::Read::read_exact(&mut inner, &mut block)?;
+    let raw = DxEntryRaw::from(&block);
+    entries.push(DxEntry {
+      hash: raw.hash,
+      block: raw.block,
+    });
+  }
+  Ok((count_limit.limit, entries))
+}
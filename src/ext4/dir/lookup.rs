@@ -0,0 +1,152 @@
+use super::{dx_hash, DirEntry, DxEntry, DxNode, DxRoot, Error};
+use crate::ext4::{
+  file_sys,
+  inode::Flags,
+  superblock::{self, HashVersion},
+  Extent, FileSystem, Inode,
+};
+use std::io;
+
+impl<R> FileSystem<R>
+where
+  R: io::Read + io::Seek,
+{
+  /// Looks up a name in a directory. Hashed directories are searched through their htree, which
+  /// only reads the blocks along the path of the name's hash. If the htree turns out to be
+  /// unusable, every leaf block is scanned instead.
+  pub fn find_dir_entry(&mut self, dir: &Inode, name: &[u8]) -> Result<Option<DirEntry>, file_sys::Error>
+  {
+    // The '.' and '..' entries live in the htree root rather than in a leaf.
+    let dots: bool = name == b"." || name == b"..";
+    if !dots && dir.flags.contains(Flags::HASHED_INDEXES) && self.sb.feature_dir_index() {
+      let extents: Vec<Extent> = self.read_extents(dir)?;
+      match self.find_dx_entry(&extents, name) {
+        Err(file_sys::Error::Dir(_)) => {}
+        result => return result,
+      }
+    }
+    for entry in self.iter_dir(dir.clone())? {
+      let entry: DirEntry = entry?;
+      if entry.name == name {
+        return Ok(Some(entry));
+      }
+    }
+    Ok(None)
+  }
+
+  /// Returns the hash version a directory's htree was built with, accounting for filesystems that
+  /// hash names as unsigned characters.
+  pub fn get_dx_hash_version(&self, root: &DxRoot) -> HashVersion
+  {
+    if self.sb.flags.contains(superblock::Flags::UNSIGNED_DIRECTORY_HASH) {
+      match root.hash_version {
+        HashVersion::Legacy => HashVersion::LegacyUnsigned,
+        HashVersion::HalfMD4 => HashVersion::HalfMD4Unsigned,
+        HashVersion::Tea => HashVersion::TeaUnsigned,
+        version => version,
+      }
+    } else {
+      root.hash_version
+    }
+  }
+
+  /// The htree hash seed as four native words.
+  pub fn get_dx_hash_seed(&self) -> [u32; 4]
+  {
+    let bytes: [u8; 16] = self.sb.hash_seed.to_bytes();
+    let mut seed: [u32; 4] = [0; 4];
+    for (word, chunk) in seed.iter_mut().zip(bytes.chunks_exact(4)) {
+      *word = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+    seed
+  }
+
+  fn find_dx_entry(&mut self, extents: &[Extent], name: &[u8]) -> Result<Option<DirEntry>, file_sys::Error>
+  {
+    let filetype: bool = self.sb.feature_filetype();
+    let block_size: u32 = self.sb.get_block_size();
+    let root = DxRoot::new(&self.read_dir_block(extents, 0)?, filetype, block_size)?;
+    let max_levels: u8 = if self.sb.feature_largedir() { 3 } else { 2 };
+    if root.indirect_levels >= max_levels {
+      return Err(Error::DxLevels(root.indirect_levels).into());
+    }
+    let version: HashVersion = self.get_dx_hash_version(&root);
+    let (hash, _) = match dx_hash(name, &version, &self.get_dx_hash_seed()) {
+      Some(hash) => hash,
+      None => return Err(Error::DxHashVersion(version).into()),
+    };
+
+    // Each frame holds the entries of one level of the tree and the one we went down through.
+    let mut frames: Vec<(Vec<DxEntry>, usize)> = Vec::with_capacity(root.indirect_levels as usize + 1);
+    let mut entries: Vec<DxEntry> = root.entries;
+    loop {
+      let at: usize = entries[1..].partition_point(|entry| entry.hash <= hash);
+      let block: u32 = entries[at].block;
+      frames.push((entries, at));
+      if frames.len() > root.indirect_levels as usize {
+        break;
+      }
+      entries = DxNode::new(&self.read_dir_block(extents, block)?)?.entries;
+    }
+
+    loop {
+      let (entries, at) = &frames[frames.len() - 1];
+      let leaf: Vec<u8> = self.read_dir_block(extents, entries[*at].block)?;
+      let mut offset: usize = 0;
+      while let Some(entry) = DirEntry::next_in_block(&leaf, &mut offset, filetype, block_size) {
+        let entry: DirEntry = entry?;
+        if entry.name == name {
+          return Ok(Some(entry));
+        }
+      }
+      if !self.next_dx_leaf(extents, &mut frames, hash)? {
+        return Ok(None);
+      }
+    }
+  }
+
+  /// Moves to the next leaf if names with the same hash may continue there, which the lowest bit
+  /// of the next entry's hash tells.
+  fn next_dx_leaf(
+    &mut self,
+    extents: &[Extent],
+    frames: &mut [(Vec<DxEntry>, usize)],
+    hash: u32,
+  ) -> Result<bool, file_sys::Error>
+  {
+    let mut level: usize = frames.len() - 1;
+    loop {
+      let (entries, at) = &mut frames[level];
+      *at += 1;
+      if *at < entries.len() {
+        break;
+      }
+      if level == 0 {
+        return Ok(false);
+      }
+      level -= 1;
+    }
+    let (entries, at) = &frames[level];
+    if entries[*at].hash & !1 != hash {
+      return Ok(false);
+    }
+    for level in level + 1..frames.len() {
+      let (entries, at) = &frames[level - 1];
+      let node = DxNode::new(&self.read_dir_block(extents, entries[*at].block)?)?;
+      frames[level] = (node.entries, 0);
+    }
+    Ok(true)
+  }
+
+  fn read_dir_block(&mut self, extents: &[Extent], block: u32) -> Result<Vec<u8>, file_sys::Error>
+  {
+    match extents
+      .iter()
+      .filter(|extent| !extent.unwritten)
+      .find_map(|extent| extent.map(block))
+    {
+      Some(block) => self.read_block(block),
+      None => Err(Error::Hole(block).into()),
+    }
+  }
+}
@@ -0,0 +1,12 @@
+mod dir_block;
+mod dir_entry;
+mod hash;
+mod htree;
+mod lookup;
+mod raw;
+
+pub use dir_block::DirBlock;
+pub use dir_entry::{DirEntry, Error};
+pub use hash::dx_hash;
+pub use htree::{DxEntry, DxNode, DxRoot};
+pub(crate) use raw::{DirEntryRaw, DxCountLimitRaw, DxEntryRaw, DxRootInfoRaw};
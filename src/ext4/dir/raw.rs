@@ -0,0 +1,147 @@
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct DirEntryRaw
+{
+  /// Number of the inode that this directory entry points to.
+  pub(crate) inode: u32, // 0 - 4
+  /// Length of this directory entry.
+  pub(crate) rec_len: u16, // 4 - 6
+  /// Length of the file name. Without the filetype feature this is a 16-bit
+  /// field that also covers the next byte.
+  pub(crate) name_len: u8, // 6 - 7
+  /// File type code, if the filetype feature is set.
+  pub(crate) file_type: u8, // 7 - 8
+}
+
+impl DirEntryRaw
+{
+  pub(crate) const WIDTH: usize = 8;
+}
+
+impl From<&[u8; Self::WIDTH]> for DirEntryRaw
+{
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.inode = u32::from_le(raw.inode);
+    raw.rec_len = u16::from_le(raw.rec_len);
+    raw
+  }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct DxRootInfoRaw
+{
+  /// Zero.
+  pub(crate) reserved_zero: u32, // 0 - 4
+  /// Hash type.
+  pub(crate) hash_version: u8, // 4 - 5
+  /// Length of the tree information, 0x8.
+  pub(crate) info_length: u8, // 5 - 6
+  /// Depth of the htree. Cannot be larger than 3 if the INCOMPAT_LARGEDIR
+  /// feature is set; cannot be larger than 2 otherwise.
+  pub(crate) indirect_levels: u8, // 6 - 7
+  pub(crate) unused_flags: u8, // 7 - 8
+}
+
+impl DxRootInfoRaw
+{
+  pub(crate) const WIDTH: usize = 8;
+}
+
+impl From<&[u8; Self::WIDTH]> for DxRootInfoRaw
+{
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.reserved_zero = u32::from_le(raw.reserved_zero);
+    raw
+  }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct DxCountLimitRaw
+{
+  /// Maximum number of dx_entries that can follow this header, plus 1 for
+  /// the header itself.
+  pub(crate) limit: u16, // 0 - 2
+  /// Actual number of dx_entries that follow this header, plus 1 for the
+  /// header itself.
+  pub(crate) count: u16, // 2 - 4
+  /// The block number (within the directory file) that goes with hash=0.
+  pub(crate) block: u32, // 4 - 8
+}
+
+impl DxCountLimitRaw
+{
+  pub(crate) const WIDTH: usize = 8;
+}
+
+impl From<&[u8; Self::WIDTH]> for DxCountLimitRaw
+{
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.limit = u16::from_le(raw.limit);
+    raw.count = u16::from_le(raw.count);
+    raw.block = u32::from_le(raw.block);
+    raw
+  }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct DxEntryRaw
+{
+  /// Hash code.
+  pub(crate) hash: u32, // 0 - 4
+  /// Block number (within the directory file, not filesystem blocks) of the
+  /// next node in the htree.
+  pub(crate) block: u32, // 4 - 8
+}
+
+impl DxEntryRaw
+{
+  pub(crate) const WIDTH: usize = 8;
+}
+
+impl From<&[u8; Self::WIDTH]> for DxEntryRaw
+{
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.hash = u32::from_le(raw.hash);
+    raw.block = u32::from_le(raw.block);
+    raw
+  }
+}
@@ -0,0 +1,194 @@
+use super::{ExtentHeaderRaw, ExtentIdxRaw, ExtentRaw};
+use crate::concat_lo_hi;
+use std::io;
+
+#[derive(Debug)]
+pub struct ExtentHeader
+{
+  /// Magic number, 0xF30A.
+  pub magic: u16,
+  /// Number of valid entries following the header.
+  pub entries: u16,
+  /// Maximum number of entries that could follow the header.
+  pub max: u16,
+  /// Depth of this extent node in the extent tree. 0 = this extent node points
+  /// to data blocks; otherwise, this extent node points to other extent nodes.
+  pub depth: u16,
+  /// Generation of the tree. (Used by Lustre, but not standard ext4).
+  pub generation: u32,
+}
+
+impl ExtentHeader
+{
+  pub const RAW_WIDTH: usize = ExtentHeaderRaw::WIDTH;
+
+  pub const MAGIC_SIGNATURE: u16 = 0xF30A;
+
+  /// The extent tree can be at most 5 levels deep.
+  pub const MAX_DEPTH: u16 = 5;
+
+  pub fn new<R>(inner: &mut R) -> Result<Self, Error>
+  where
+    R: io::Read,
+  {
+    let mut block: [u8; Self::RAW_WIDTH] = [0; Self::RAW_WIDTH];
+    inner.read_exact(&mut block)?;
+    Ok(ExtentHeaderRaw::from(&block).into())
+  }
+
+  pub fn check_signature(&self) -> Result<(), Error>
+  {
+    if self.magic == Self::MAGIC_SIGNATURE {
+      Ok(())
+    } else {
+      Err(Error::Signature(self.magic))
+    }
+  }
+}
+
+impl From<ExtentHeaderRaw> for ExtentHeader
+{
+  fn from(raw: ExtentHeaderRaw) -> Self
+  {
+    Self {
+      magic: raw.eh_magic,
+      entries: raw.eh_entries,
+      max: raw.eh_max,
+      depth: raw.eh_depth,
+      generation: raw.eh_generation,
+    }
+  }
+}
+
+/// Internal node of the extent tree.
+#[derive(Debug)]
+pub struct ExtentIdx
+{
+  /// This index node covers file blocks from 'block' onward.
+  pub block: u32,
+  /// Block number of the extent node that is the next level lower in the tree.
+  pub leaf: u64,
+}
+
+impl ExtentIdx
+{
+  pub const RAW_WIDTH: usize = ExtentIdxRaw::WIDTH;
+
+  pub fn new<R>(inner: &mut R) -> Result<Self, Error>
+  where
+    R: io::Read,
+  {
+    let mut block: [u8; Self::RAW_WIDTH] = [0; Self::RAW_WIDTH];
+    inner.read_exact(&mut block)?;
+    Ok(ExtentIdxRaw::from(&block).into())
+  }
+}
+
+impl From<ExtentIdxRaw> for ExtentIdx
+{
+  fn from(raw: ExtentIdxRaw) -> Self
+  {
+    Self {
+      block: raw.ei_block,
+      leaf: concat_lo_hi!(u64, raw.ei_leaf_lo, raw.ei_leaf_hi),
+    }
+  }
+}
+
+/// A run of logically contiguous file blocks that are also physically contiguous on disk.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Extent
+{
+  /// First file block number that this extent covers.
+  pub block: u32,
+  /// Number of blocks covered by extent.
+  pub len: u32,
+  /// Block number to which this extent points.
+  pub start: u64,
+  /// Unwritten (preallocated) extents have blocks reserved on disk, but read back as zeroes.
+  pub unwritten: bool,
+}
+
+impl Extent
+{
+  pub const RAW_WIDTH: usize = ExtentRaw::WIDTH;
+
+  /// Maximum length of an initialized extent. Leaf entries with a longer length are
+  /// uninitialized and their actual length is `ee_len - INIT_MAX_LEN`.
+  pub const INIT_MAX_LEN: u16 = 32768;
+
+  pub fn new<R>(inner: &mut R) -> Result<Self, Error>
+  where
+    R: io::Read,
+  {
+    let mut block: [u8; Self::RAW_WIDTH] = [0; Self::RAW_WIDTH];
+    inner.read_exact(&mut block)?;
+    Ok(ExtentRaw::from(&block).into())
+  }
+
+  /// Returns the physical block backing the given file block, if this extent covers it.
+  pub fn map(&self, block: u32) -> Option<u64>
+  {
+    if block >= self.block && ((block - self.block) as u64) < self.len as u64 {
+      Some(self.start + (block - self.block) as u64)
+    } else {
+      None
+    }
+  }
+}
+
+impl From<ExtentRaw> for Extent
+{
+  fn from(raw: ExtentRaw) -> Self
+  {
+    let unwritten: bool = raw.ee_len > Self::INIT_MAX_LEN;
+    Self {
+      block: raw.ee_block,
+      len: if unwritten {
+        (raw.ee_len - Self::INIT_MAX_LEN) as u32
+      } else {
+        raw.ee_len as u32
+      },
+      start: concat_lo_hi!(u64, raw.ee_start_lo, raw.ee_start_hi),
+      unwritten,
+    }
+  }
+}
+
+#[derive(Debug)]
+pub enum Error
+{
+  IO(io::Error),
+  Signature(u16),
+  Depth(u16),
+  Entries(u16),
+}
+
+impl From<io::Error> for Error
+{
+  fn from(error: io::Error) -> Self
+  {
+    Self::IO(error)
+  }
+}
+
+impl std::fmt::Display for Error
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    write!(
+      f,
+      "Extent error: {}",
+      match self {
+        Self::IO(error) => format!("An IO error occurred: {}", error),
+        Self::Signature(magic) => format!(
+          "Expected magic number was {:#06X} but found {:#06X}.",
+          ExtentHeader::MAGIC_SIGNATURE,
+          magic
+        ),
+        Self::Depth(depth) => format!("Extent node has an invalid depth of {}.", depth),
+        Self::Entries(entries) => format!("Extent node claims {} entries, more than fit in it.", entries),
+      }
+    )
+  }
+}
@@ -0,0 +1,7 @@
+mod extent;
+mod raw;
+mod tree;
+
+pub use extent::{Error, Extent, ExtentHeader, ExtentIdx};
+pub(crate) use raw::{ExtentHeaderRaw, ExtentIdxRaw, ExtentRaw};
+pub use tree::ExtentBlock;
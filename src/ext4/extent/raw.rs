@@ -0,0 +1,128 @@
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct ExtentHeaderRaw
+{
+  /// Magic number, 0xF30A.
+  pub(crate) eh_magic: u16, // 0 - 2
+  /// Number of valid entries following the header.
+  pub(crate) eh_entries: u16, // 2 - 4
+  /// Maximum number of entries that could follow the header.
+  pub(crate) eh_max: u16, // 4 - 6
+  /// Depth of this extent node in the extent tree. 0 = this extent node points
+  /// to data blocks; otherwise, this extent node points to other extent nodes.
+  /// The extent tree can be at most 5 levels deep: a logical block number can
+  /// be at most 2^32, and the smallest n that satisfies 4*(((blocksize -
+  /// 12)/12)^n) >= 2^32 is 5.
+  pub(crate) eh_depth: u16, // 6 - 8
+  /// Generation of the tree. (Used by Lustre, but not standard ext4).
+  pub(crate) eh_generation: u32, // 8 - 12
+}
+
+impl ExtentHeaderRaw
+{
+  pub(crate) const WIDTH: usize = 12;
+}
+
+impl From<&[u8; Self::WIDTH]> for ExtentHeaderRaw
+{
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.eh_magic = u16::from_le(raw.eh_magic);
+    raw.eh_entries = u16::from_le(raw.eh_entries);
+    raw.eh_max = u16::from_le(raw.eh_max);
+    raw.eh_depth = u16::from_le(raw.eh_depth);
+    raw.eh_generation = u32::from_le(raw.eh_generation);
+    raw
+  }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct ExtentIdxRaw
+{
+  /// This index node covers file blocks from 'block' onward.
+  pub(crate) ei_block: u32, // 0 - 4
+  /// Lower 32-bits of the block number of the extent node that is the next
+  /// level lower in the tree. The tree node pointed to can be either another
+  /// internal node or a leaf node, described below.
+  pub(crate) ei_leaf_lo: u32, // 4 - 8
+  /// Upper 16-bits of the previous field.
+  pub(crate) ei_leaf_hi: u16, // 8 - 10
+  pub(crate) ei_unused: u16, // 10 - 12
+}
+
+impl ExtentIdxRaw
+{
+  pub(crate) const WIDTH: usize = 12;
+}
+
+impl From<&[u8; Self::WIDTH]> for ExtentIdxRaw
+{
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.ei_block = u32::from_le(raw.ei_block);
+    raw.ei_leaf_lo = u32::from_le(raw.ei_leaf_lo);
+    raw.ei_leaf_hi = u16::from_le(raw.ei_leaf_hi);
+    raw.ei_unused = u16::from_le(raw.ei_unused);
+    raw
+  }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct ExtentRaw
+{
+  /// First file block number that this extent covers.
+  pub(crate) ee_block: u32, // 0 - 4
+  /// Number of blocks covered by extent. If the value of this field is <=
+  /// 32768, the extent is initialized. If the value of the field is > 32768,
+  /// the extent is uninitialized and the actual extent length is ee_len -
+  /// 32768. Therefore, the maximum length of a initialized extent is 32768
+  /// blocks, and the maximum length of an uninitialized extent is 32767.
+  pub(crate) ee_len: u16, // 4 - 6
+  /// Upper 16-bits of the block number to which this extent points.
+  pub(crate) ee_start_hi: u16, // 6 - 8
+  /// Lower 32-bits of the block number to which this extent points.
+  pub(crate) ee_start_lo: u32, // 8 - 12
+}
+
+impl ExtentRaw
+{
+  pub(crate) const WIDTH: usize = 12;
+}
+
+impl From<&[u8; Self::WIDTH]> for ExtentRaw
+{
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.ee_block = u32::from_le(raw.ee_block);
+    raw.ee_len = u16::from_le(raw.ee_len);
+    raw.ee_start_hi = u16::from_le(raw.ee_start_hi);
+    raw.ee_start_lo = u32::from_le(raw.ee_start_lo);
+    raw
+  }
+}
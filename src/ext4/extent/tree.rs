@@ -0,0 +1,146 @@
+use super::{Error, Extent, ExtentHeader, ExtentIdx};
+use crate::{
+  crc,
+  ext4::{file_sys, ChecksumError, FileSystem, Inode},
+};
+use std::io;
+
+/// Block of an extent tree below the root, which ends with a checksum when metadata_csum is
+/// enabled.
+#[derive(Debug, Clone)]
+pub struct ExtentBlock
+{
+  /// Location of the block.
+  pub block: u64,
+  /// Depth of the node the block holds. Leaves are at depth 0.
+  pub depth: u16,
+  /// Checksum stored in the tail past the last entry the node has room for.
+  pub checksum: u32,
+  /// Checksum computed over the block when it was read, if the filesystem has metadata_csum.
+  pub calculated_checksum: Option<u32>,
+}
+
+impl ExtentBlock
+{
+  fn new(node: &[u8], block: u64, header: &ExtentHeader, seed: Option<u32>) -> Self
+  {
+    // The tail sits right past the room for `max` entries.
+    let tail: usize = (ExtentHeader::RAW_WIDTH + header.max as usize * Extent::RAW_WIDTH).min(node.len());
+    let checksum: u32 = node
+      .get(tail..tail + 4)
+      .map_or(0, |csum| u32::from_le_bytes([csum[0], csum[1], csum[2], csum[3]]));
+    Self {
+      block,
+      depth: header.depth,
+      checksum,
+      calculated_checksum: seed.map(|seed| crc::crc32c(seed, &node[..tail])),
+    }
+  }
+
+  /// Compares the checksum with the one computed when the block was read. A mismatch means the
+  /// block does not belong to the inode anymore, or never held an extent node.
+  pub fn check_checksum(&self) -> Option<ChecksumError>
+  {
+    match self.calculated_checksum {
+      Some(calculated) if calculated != self.checksum => Some(ChecksumError::Mismatch(self.checksum, calculated)),
+      _ => None,
+    }
+  }
+}
+
+impl<R> FileSystem<R>
+where
+  R: io::Read + io::Seek,
+{
+  /// Walks the extent tree rooted in the inode's `i_block` and returns the extents found in its
+  /// leaves, ordered by file block.
+  pub fn read_extent_tree(&mut self, inode: &Inode) -> Result<Vec<Extent>, file_sys::Error>
+  {
+    self.read_extent_tree_with(inode, &mut |fs: &mut Self, block: u64| fs.read_block(block).map(Some))
+  }
+
+  /// Same as `read_extent_tree`, but the blocks below the root are read through `read`. A block it
+  /// returns `None` for is left as a hole.
+  pub(crate) fn read_extent_tree_with<F>(&mut self, inode: &Inode, read: &mut F) -> Result<Vec<Extent>, file_sys::Error>
+  where
+    F: FnMut(&mut Self, u64) -> Result<Option<Vec<u8>>, file_sys::Error>,
+  {
+    let mut extents: Vec<Extent> = Vec::new();
+    let mut tree = ExtentTree {
+      seed: inode.checksum_seed,
+      extents: &mut extents,
+      blocks: &mut Vec::new(),
+    };
+    self.read_extent_node(&inode.get_block_bytes(), None, &mut tree, read)?;
+    Ok(extents)
+  }
+
+  /// Walks the extent tree rooted in the inode's `i_block` and returns the blocks below the root,
+  /// along with how they fared against their checksum. The root is covered by the inode checksum.
+  pub fn read_extent_blocks(&mut self, inode: &Inode) -> Result<Vec<ExtentBlock>, file_sys::Error>
+  {
+    let mut blocks: Vec<ExtentBlock> = Vec::new();
+    let mut tree = ExtentTree {
+      seed: inode.checksum_seed,
+      extents: &mut Vec::new(),
+      blocks: &mut blocks,
+    };
+    self.read_extent_node(
+      &inode.get_block_bytes(),
+      None,
+      &mut tree,
+      &mut |fs: &mut Self, block: u64| fs.read_block(block).map(Some),
+    )?;
+    Ok(blocks)
+  }
+
+  fn read_extent_node<F>(
+    &mut self,
+    node: &[u8],
+    parent: Option<(u64, u16)>,
+    tree: &mut ExtentTree<'_>,
+    read: &mut F,
+  ) -> Result<(), file_sys::Error>
+  where
+    F: FnMut(&mut Self, u64) -> Result<Option<Vec<u8>>, file_sys::Error>,
+  {
+    let mut inner: &[u8] = node;
+    let header = ExtentHeader::new(&mut inner)?;
+    header.check_signature()?;
+    // Every level must be exactly one shallower than its parent, which also guarantees that a
+    // corrupted tree cannot send us around in circles.
+    if header.depth > ExtentHeader::MAX_DEPTH || parent.is_some_and(|(_, depth)| header.depth + 1 != depth) {
+      return Err(Error::Depth(header.depth).into());
+    }
+    if header.entries as usize > inner.len() / Extent::RAW_WIDTH {
+      return Err(Error::Entries(header.entries).into());
+    }
+    if let Some((block, _)) = parent {
+      tree.blocks.push(ExtentBlock::new(node, block, &header, tree.seed));
+    }
+
+    if header.depth == 0 {
+      for _ in 0..header.entries {
+        tree.extents.push(Extent::new(&mut inner)?);
+      }
+    } else {
+      let indexes = (0..header.entries)
+        .map(|_| ExtentIdx::new(&mut inner))
+        .collect::<Result<Vec<ExtentIdx>, Error>>()?;
+      for idx in indexes {
+        if let Some(block) = read(self, idx.leaf)? {
+          self.read_extent_node(&block, Some((idx.leaf, header.depth)), tree, read)?;
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+/// What a walk of an extent tree collects.
+struct ExtentTree<'a>
+{
+  seed: Option<u32>,
+  extents: &'a mut Vec<Extent>,
+  blocks: &'a mut Vec<ExtentBlock>,
+}
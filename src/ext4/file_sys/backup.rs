@@ -0,0 +1,147 @@
+use super::{Error, FileSystem};
+use crate::ext4::Superblock;
+use std::collections::HashSet;
+use std::io::{self, SeekFrom};
+
+/// Copy of the superblock found at the start of a block group.
+#[derive(Debug)]
+pub struct BackupSuperblock
+{
+  /// Block group the copy belongs to. Group 0 holds the primary superblock.
+  pub group: u32,
+  /// First block of the group, which the superblock lives in.
+  pub block: u64,
+  pub sb: Superblock,
+}
+
+impl BackupSuperblock
+{
+  /// Returns the byte offset of the superblock from the start of the filesystem.
+  pub fn get_offset(&self) -> u64
+  {
+    if self.group == 0 {
+      FileSystem::<()>::START_OFFSET
+    } else {
+      self.block * self.sb.get_block_size() as u64
+    }
+  }
+}
+
+impl<R> FileSystem<R>
+where
+  R: io::Read + io::Seek,
+{
+  /// Searches the image for copies of the superblock, for when the primary one is damaged.
+  ///
+  /// The layout of the filesystem is unknown at this point, so every block size is tried, along
+  /// with the default of `8 * block size` blocks per group. Only groups 0, 1 and the powers of 3, 5
+  /// and 7 are looked at first, as they hold a copy whatever the features are. Each copy found then
+  /// tells where the others are, which covers filesystems without sparse_super, with sparse_super2,
+  /// or with a custom group size. A copy only counts if it agrees with where it was found. Copies
+  /// with a valid checksum are listed first.
+  pub fn find_backup_superblocks(inner: &mut R, offset: u64) -> Result<Vec<BackupSuperblock>, Error>
+  {
+    let len: u64 = inner.seek(SeekFrom::End(0))?.saturating_sub(offset);
+    let mut found: Vec<BackupSuperblock> = Vec::new();
+    for log_block_size in Superblock::MIN_BLOCK_LOG_SIZE..=Superblock::MAX_BLOCK_LOG_SIZE {
+      let block_size: u64 = 1 << log_block_size;
+      let first_data_block: u64 = if block_size == 1024 { 1 } else { 0 };
+      let mut probed: HashSet<(u64, u64)> = HashSet::new();
+      let mut layouts: Vec<u64> = vec![8 * block_size];
+      let mut layout: usize = 0;
+      while layout < layouts.len() {
+        let blocks_per_group: u64 = layouts[layout];
+        layout += 1;
+        let groups: u64 = (len / block_size)
+          .saturating_sub(first_data_block)
+          .div_ceil(blocks_per_group);
+        let mut candidates: Vec<u32> = (0..groups.min(u32::MAX as u64) as u32)
+          .filter(|&group| Superblock::is_sparse_group(group))
+          .collect();
+
+        let mut candidate: usize = 0;
+        while candidate < candidates.len() {
+          let group: u32 = candidates[candidate];
+          candidate += 1;
+          let block: u64 = first_data_block + group as u64 * blocks_per_group;
+          let at: u64 = if group == 0 {
+            Self::START_OFFSET
+          } else {
+            block * block_size
+          };
+          if !probed.insert((blocks_per_group, at)) {
+            continue;
+          }
+          let backup = BackupSuperblock {
+            group,
+            block,
+            sb: match Self::probe_superblock(inner, offset + at, group)? {
+              Some(sb) => sb,
+              None => continue,
+            },
+          };
+          if backup.sb.get_block_size() as u64 != block_size || backup.sb.first_data_block as u64 != first_data_block {
+            continue;
+          }
+          // Only group 0 can be trusted to sit in the same place whatever the group size is.
+          if backup.sb.blocks_per_group as u64 != blocks_per_group {
+            if group == 0 && !layouts.contains(&(backup.sb.blocks_per_group as u64)) {
+              layouts.push(backup.sb.blocks_per_group as u64);
+            }
+            continue;
+          }
+          for group in backup.sb.get_backup_groups() {
+            if !candidates.contains(&group) {
+              candidates.push(group);
+            }
+          }
+          found.push(backup);
+        }
+      }
+    }
+    // Copies whose checksum does not match come last, as they are the least trustworthy.
+    found.sort_by_key(|backup| {
+      (
+        backup.sb.check_checksum().is_some(),
+        backup.group,
+        backup.sb.get_block_size(),
+      )
+    });
+    Ok(found)
+  }
+
+  /// Reads the superblock at the given byte offset of the image, if it looks like the copy kept by
+  /// the given group.
+  fn probe_superblock(inner: &mut R, at: u64, group: u32) -> Result<Option<Superblock>, Error>
+  {
+    inner.seek(SeekFrom::Start(at))?;
+    let sb: Superblock = match Superblock::new(inner) {
+      Ok(sb) => sb,
+      Err(_) => return Ok(None),
+    };
+    if sb.check_signature().is_some()
+      || sb.log_block_size > Superblock::MAX_BLOCK_LOG_SIZE - Superblock::MIN_BLOCK_LOG_SIZE
+      || sb.blocks_per_group == 0
+      || (group != 0 && sb.block_group_nr as u32 != group % 0x10000)
+    {
+      return Ok(None);
+    }
+    Ok(Some(sb))
+  }
+
+  /// Opens the filesystem through the copy of the superblock kept by the given block group, along
+  /// with the copy of the group descriptor table that follows it.
+  pub fn from_backup(mut inner: R, offset: u64, group: u32) -> Result<Self, Error>
+  {
+    let backup: BackupSuperblock = Self::find_backup_superblocks(&mut inner, offset)?
+      .into_iter()
+      .find(|backup| backup.group == group)
+      .ok_or(Error::NoBackup(group))?;
+    Ok(Self {
+      inner,
+      offset,
+      sb_group: group,
+      sb: backup.sb,
+    })
+  }
+}
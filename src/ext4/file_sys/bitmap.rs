@@ -0,0 +1,81 @@
+use super::{Error, FileSystem};
+use crate::{
+  crc,
+  ext4::{ChecksumError, GroupDesc},
+};
+use std::io;
+
+/// Block or inode bitmap of a block group.
+#[derive(Debug, Clone)]
+pub struct Bitmap
+{
+  /// The whole block the bitmap lives in. Only the bits for the clusters or inodes of the group
+  /// are meaningful.
+  pub data: Vec<u8>,
+  /// Checksum stored in the group descriptor. Only its low 16 bits are stored without 64bit.
+  pub checksum: u32,
+  /// Checksum computed over the bitmap when it was read, if the filesystem has metadata_csum.
+  pub calculated_checksum: Option<u32>,
+}
+
+impl Bitmap
+{
+  /// Whether the given bit is set, which marks its cluster or inode as in use. Bits past the end
+  /// of the block count as set.
+  pub fn is_set(&self, bit: u64) -> bool
+  {
+    self
+      .data
+      .get((bit / 8) as usize)
+      .map_or(true, |byte| byte & (1 << (bit % 8)) != 0)
+  }
+
+  /// Compares the checksum with the one computed when the bitmap was read. A mismatch means the
+  /// bitmap was written after its group descriptor was, as when the filesystem was not cleanly
+  /// unmounted.
+  pub fn check_checksum(&self) -> Option<ChecksumError>
+  {
+    match self.calculated_checksum {
+      Some(calculated) if calculated != self.checksum => Some(ChecksumError::Mismatch(self.checksum, calculated)),
+      _ => None,
+    }
+  }
+}
+
+impl<R> FileSystem<R>
+where
+  R: io::Read + io::Seek,
+{
+  /// Reads the block bitmap of a group, whose checksum covers one bit per cluster of the group.
+  /// Groups flagged BLOCK_UNINIT have no bitmap on disk.
+  pub fn read_block_bitmap(&mut self, desc: &GroupDesc) -> Result<Bitmap, Error>
+  {
+    let len: usize = (self.sb.clusters_per_group / 8) as usize;
+    self.read_bitmap(desc.block_bitmap, desc.block_bitmap_csum, len)
+  }
+
+  /// Reads the inode bitmap of a group, whose checksum covers one bit per inode of the group.
+  /// Groups flagged INODE_UNINIT have no bitmap on disk.
+  pub fn read_inode_bitmap(&mut self, desc: &GroupDesc) -> Result<Bitmap, Error>
+  {
+    let len: usize = (self.sb.inodes_per_group / 8) as usize;
+    self.read_bitmap(desc.inode_bitmap, desc.inode_bitmap_csum, len)
+  }
+
+  fn read_bitmap(&mut self, block: u64, checksum: u32, len: usize) -> Result<Bitmap, Error>
+  {
+    let data: Vec<u8> = self.read_block(block)?;
+    let calculated_checksum: Option<u32> = if self.sb.feature_metadata_csum() {
+      let csum: u32 = crc::crc32c(self.sb.get_checksum_seed(), &data[..len.min(data.len())]);
+      // 32-byte group descriptors only have room for the low half.
+      Some(if self.sb.feature_64bit() { csum } else { csum & 0xFFFF })
+    } else {
+      None
+    };
+    Ok(Bitmap {
+      data,
+      checksum,
+      calculated_checksum,
+    })
+  }
+}
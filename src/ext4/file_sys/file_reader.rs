@@ -0,0 +1,133 @@
+use super::{Error, FileSystem};
+use crate::ext4::{Extent, Inode};
+use std::io::{self, Seek, SeekFrom};
+
+/// Reads the contents of a file through its extents. Data blocks are only read when they are
+/// asked for, holes and unwritten extents read back as zeroes and nothing past `i_size` is
+/// returned. Inline data and the targets of fast symbolic links are served from the inode instead.
+pub struct FileReader<'fs, R>
+{
+  fs: &'fs mut FileSystem<R>,
+  inode: Inode,
+  extents: Vec<Extent>,
+  inline: Option<Vec<u8>>,
+  pos: u64,
+}
+
+impl<'fs, R> FileReader<'fs, R>
+where
+  R: io::Read + io::Seek,
+{
+  pub fn new(fs: &'fs mut FileSystem<R>, inode: Inode) -> Result<Self, Error>
+  {
+    let extents: Vec<Extent> = fs.read_extents(&inode)?;
+    Self::from_extents(fs, inode, extents)
+  }
+
+  /// Reads the inode through the given extents instead of the ones it maps itself, such as what
+  /// is left of a deleted inode's.
+  pub fn from_extents(fs: &'fs mut FileSystem<R>, inode: Inode, extents: Vec<Extent>) -> Result<Self, Error>
+  {
+    let inline: Option<Vec<u8>> = if inode.is_fast_symlink() {
+      Some(inode.get_block_bytes()[..inode.size as usize].to_vec())
+    } else {
+      inode.get_inline_data()?
+    };
+    Ok(Self {
+      fs,
+      inode,
+      extents,
+      inline,
+      pos: 0,
+    })
+  }
+
+  pub fn inode(&self) -> &Inode
+  {
+    &self.inode
+  }
+
+  pub fn extents(&self) -> &[Extent]
+  {
+    &self.extents
+  }
+
+  pub fn into_inode(self) -> Inode
+  {
+    self.inode
+  }
+}
+
+impl<R> io::Read for FileReader<'_, R>
+where
+  R: io::Read + io::Seek,
+{
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>
+  {
+    if self.pos >= self.inode.size || buf.is_empty() {
+      return Ok(0);
+    }
+    if let Some(data) = &self.inline {
+      let len: usize = (buf.len() as u64).min(self.inode.size - self.pos) as usize;
+      let start: usize = (self.pos as usize).min(data.len());
+      let copied: usize = len.min(data.len() - start);
+      buf[..copied].copy_from_slice(&data[start..start + copied]);
+      buf[copied..len].iter_mut().for_each(|b| *b = 0);
+      self.pos += len as u64;
+      return Ok(len);
+    }
+    let block_size: u64 = self.fs.sb.get_block_size() as u64;
+    let block: u64 = self.pos / block_size;
+    let len: u64 = (buf.len() as u64).min(self.inode.size - self.pos);
+
+    // The first extent that ends past the current block either covers it or is the next one
+    // after the hole we are in.
+    let idx: usize = self
+      .extents
+      .partition_point(|extent| extent.block as u64 + extent.len as u64 <= block);
+    let len: usize = match self.extents.get(idx) {
+      Some(extent) if extent.block as u64 <= block => {
+        let end: u64 = (extent.block as u64 + extent.len as u64) * block_size;
+        let len: usize = len.min(end - self.pos) as usize;
+        if extent.unwritten {
+          buf[..len].iter_mut().for_each(|b| *b = 0);
+        } else {
+          let offset: u64 = (extent.start + block - extent.block as u64) * block_size + self.pos % block_size;
+          self.fs.seek(SeekFrom::Start(offset))?;
+          self.fs.inner.read_exact(&mut buf[..len])?;
+        }
+        len
+      }
+      next => {
+        let end: u64 = next.map_or(u64::MAX, |extent| extent.block as u64 * block_size);
+        let len: usize = len.min(end - self.pos) as usize;
+        buf[..len].iter_mut().for_each(|b| *b = 0);
+        len
+      }
+    };
+    self.pos += len as u64;
+    Ok(len)
+  }
+}
+
+impl<R> io::Seek for FileReader<'_, R>
+{
+  fn seek(&mut self, pos: SeekFrom) -> io::Result<u64>
+  {
+    let pos: Option<u64> = match pos {
+      SeekFrom::Start(pos) => Some(pos),
+      SeekFrom::End(offset) => self.inode.size.checked_add_signed(offset),
+      SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+    };
+    match pos {
+      Some(pos) => {
+        self.pos = pos;
+        Ok(pos)
+      }
+      None => Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "invalid seek to a negative or overflowing position",
+      )),
+    }
+  }
+}
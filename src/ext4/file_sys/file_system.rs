@@ -1,12 +1,18 @@
-use super::iters;
-use crate::ext4::{superblock, Superblock};
-use std::io::{self, SeekFrom};
+use super::{iters, FileReader};
+use crate::{
+  ext4::{dir, extent, group_desc, inode, jbd2, superblock, xattr, GroupDesc, Inode, Superblock},
+  partition::{self, PartitionTable},
+};
+use std::io::{self, Seek, SeekFrom};
 
 pub struct FileSystem<R>
 {
   pub(crate) inner: R,
-  offset: u64,
+  pub(crate) offset: u64,
   pub sb: Superblock,
+  /// Block group whose copy of the superblock is in use. The group descriptor table is read from
+  /// the copy kept along with it.
+  pub(crate) sb_group: u32,
 }
 
 impl<R> FileSystem<R>
@@ -19,13 +25,184 @@ impl<R> FileSystem<R>
   {
     inner.seek(SeekFrom::Start(Self::START_OFFSET + offset))?;
     let sb = Superblock::new(&mut inner)?;
-    Ok(Self { inner, offset, sb })
+    Ok(Self {
+      inner,
+      offset,
+      sb,
+      sb_group: 0,
+    })
+  }
+
+  /// Opens the filesystem in the partition with the given number of a whole-disk image.
+  pub fn from_partition(mut inner: R, number: u32) -> Result<Self, Error>
+  where
+    R: io::Read + io::Seek,
+  {
+    let offset: u64 = Self::get_partition_offset(&mut inner, number)?;
+    Self::new(inner, offset)
+  }
+
+  /// Returns the byte offset of the partition with the given number of a whole-disk image.
+  pub fn get_partition_offset(inner: &mut R, number: u32) -> Result<u64, Error>
+  where
+    R: io::Read + io::Seek,
+  {
+    let table = PartitionTable::new(inner)?;
+    Ok(table.get(number).ok_or(partition::Error::NotFound(number))?.offset)
   }
 
   pub fn iter_group_descriptors<'fs>(&'fs mut self) -> iters::GroupDescIter<'fs, R>
   {
     iters::GroupDescIter::new(self)
   }
+
+  /// Walks the inode table of every group and yields the inodes that are deleted or orphaned.
+  pub fn iter_deleted_inodes(&mut self) -> iters::DeletedInodeIter<'_, R>
+  where
+    R: io::Read + io::Seek,
+  {
+    iters::DeletedInodeIter::new(self)
+  }
+
+  /// Iterates over the entries of the given directory inode.
+  pub fn iter_dir(&mut self, inode: Inode) -> Result<iters::DirIter<'_, R>, Error>
+  where
+    R: io::Read + io::Seek,
+  {
+    iters::DirIter::new(self, inode)
+  }
+
+  /// Opens the contents of the given inode for reading.
+  pub fn open_inode(&mut self, inode: Inode) -> Result<FileReader<'_, R>, Error>
+  where
+    R: io::Read + io::Seek,
+  {
+    FileReader::new(self, inode)
+  }
+
+  /// Reads the filesystem block with the given number.
+  pub fn read_block(&mut self, block: u64) -> Result<Vec<u8>, Error>
+  where
+    R: io::Read + io::Seek,
+  {
+    let block_size: u64 = self.sb.get_block_size() as u64;
+    let mut buf: Vec<u8> = vec![0; block_size as usize];
+    self.seek(SeekFrom::Start(block * block_size))?;
+    self.inner.read_exact(&mut buf)?;
+    Ok(buf)
+  }
+
+  /// Reads the descriptor of the given block group from the group descriptor table, and computes
+  /// its checksum.
+  pub fn read_group_desc(&mut self, group: u32) -> Result<GroupDesc, Error>
+  where
+    R: io::Read + io::Seek,
+  {
+    if group >= self.sb.get_group_count() {
+      return Err(Error::InvalidGroup(group));
+    }
+    let offset: u64 = self.get_group_desc_offset(group);
+    self.seek(SeekFrom::Start(offset))?;
+    let mut raw: Vec<u8> = vec![0; self.sb.get_desc_size() as usize];
+    self.inner.read_exact(&mut raw)?;
+    let mut desc: GroupDesc = GroupDesc::new(&mut raw.as_slice(), self.sb.feature_64bit())?;
+    desc.calculated_checksum = GroupDesc::calculate_checksum(&self.sb, group, &raw);
+    Ok(desc)
+  }
+
+  /// Returns the byte offset of the descriptor of the given block group.
+  ///
+  /// The table is made of whole blocks of descriptors. Without meta_bg, all of them follow the
+  /// superblock. With meta_bg, the blocks from `first_meta_bg` on are each kept by the meta group
+  /// of groups they describe, in its first group with backups in its second and last ones. The
+  /// second group's copy is read when the filesystem was opened through a backup superblock.
+  fn get_group_desc_offset(&self, group: u32) -> u64
+  {
+    let block_size: u64 = self.sb.get_block_size() as u64;
+    let desc_size: u64 = self.sb.get_desc_size() as u64;
+    let per_block: u64 = (block_size / desc_size).max(1);
+    let nr: u64 = group as u64 / per_block;
+    let block: u64 = if !self.sb.feature_meta_bg() || nr < self.sb.first_meta_bg as u64 {
+      let sb_block: u64 = if self.sb_group == 0 {
+        Self::START_OFFSET / block_size
+      } else {
+        self.sb.first_data_block as u64 + self.sb_group as u64 * self.sb.blocks_per_group as u64
+      };
+      sb_block + 1 + nr
+    } else {
+      let mut bg: u64 = nr * per_block;
+      if self.sb_group != 0 && bg + 1 < self.sb.get_group_count() as u64 {
+        bg += 1;
+      }
+      let mut has_super: u64 = self.sb.has_backup(bg as u32) as u64;
+      // With 1KiB blocks and no block before the first group, the superblock still takes block 1.
+      if block_size == 1024 && bg == 0 && self.sb.first_data_block == 0 {
+        has_super += 1;
+      }
+      self.sb.first_data_block as u64 + bg * self.sb.blocks_per_group as u64 + has_super
+    };
+    block * block_size + group as u64 % per_block * desc_size
+  }
+
+  /// Reads the inode with the given number from the inode table of the block group it belongs
+  /// to.
+  pub fn read_inode(&mut self, ino: u32) -> Result<Inode, Error>
+  where
+    R: io::Read + io::Seek,
+  {
+    let offset: u64 = self.get_inode_offset(ino)?;
+    let mut block: Vec<u8> = vec![0; self.sb.get_inode_size() as usize];
+    self.seek(SeekFrom::Start(offset))?;
+    self.inner.read_exact(&mut block)?;
+    self.parse_inode(ino, &block)
+  }
+
+  /// Returns the byte offset of an inode in the filesystem, through its group's inode table.
+  pub fn get_inode_offset(&mut self, ino: u32) -> Result<u64, Error>
+  where
+    R: io::Read + io::Seek,
+  {
+    if ino == 0 || ino > self.sb.inodes_count || self.sb.inodes_per_group == 0 {
+      return Err(Error::InvalidInode(ino));
+    }
+    let group: u32 = (ino - 1) / self.sb.inodes_per_group;
+    let index: u32 = (ino - 1) % self.sb.inodes_per_group;
+    let inode_table: u64 = self.read_group_desc(group)?.inode_table;
+    Ok(inode_table * self.sb.get_block_size() as u64 + index as u64 * self.sb.get_inode_size() as u64)
+  }
+
+  /// Decodes the inode with the given number out of its on-disk bytes, which span the whole inode
+  /// size, and computes its checksum.
+  pub(crate) fn parse_inode(&self, ino: u32, raw: &[u8]) -> Result<Inode, Error>
+  {
+    let inode_size: usize = raw.len();
+    let mut block: Vec<u8> = vec![0; inode_size.max(Inode::RAW_WIDTH)];
+    block[..inode_size].copy_from_slice(raw);
+
+    // The fields past the original ext2 inode are only valid up to `i_extra_isize`, so anything
+    // beyond it is cleared before the large layout is decoded.
+    let base: usize = Inode::GOOD_OLD_INODE_SIZE as usize;
+    let extra_isize: usize = if inode_size >= base + 2 {
+      u16::from_le_bytes([block[base], block[base + 1]]) as usize
+    } else {
+      0
+    };
+    let mut inode: Inode = if extra_isize == 0 || base + extra_isize > inode_size {
+      Inode::new(&mut &block[..], false, &self.sb.creator_os)?
+    } else {
+      let mut large: [u8; Inode::RAW_WIDTH_LARGE] = [0; Inode::RAW_WIDTH_LARGE];
+      let len: usize = (base + extra_isize).min(Inode::RAW_WIDTH_LARGE);
+      large[..len].copy_from_slice(&block[..len]);
+      let mut inode: Inode = Inode::new(&mut &large[..], true, &self.sb.creator_os)?;
+      inode.xattr_space = block[base + extra_isize..inode_size].to_vec();
+      inode
+    };
+    inode.calculated_checksum = Inode::calculate_checksum(&self.sb, ino, raw);
+    if self.sb.feature_metadata_csum() {
+      inode.checksum_seed = Some(Inode::calculate_checksum_seed(&self.sb, ino, inode.generation));
+    }
+    Ok(inode)
+  }
 }
 
 impl<R> io::Seek for FileSystem<R>
@@ -46,6 +223,22 @@ pub enum Error
 {
   IO(io::Error),
   Superblock(superblock::Error),
+  GroupDesc(group_desc::Error),
+  Inode(inode::Error),
+  Extent(extent::Error),
+  Dir(dir::Error),
+  Xattr(xattr::Error),
+  Journal(jbd2::Error),
+  Partition(partition::Error),
+  InvalidInode(u32),
+  InvalidGroup(u32),
+  NotFound(String),
+  NotADirectory(String),
+  SymlinkLoop,
+  EncryptedSymlink,
+  NotASymlink,
+  SymlinkTooLong(u64),
+  NoBackup(u32),
 }
 
 impl From<io::Error> for Error
@@ -56,6 +249,14 @@ impl From<io::Error> for Error
   }
 }
 
+impl From<partition::Error> for Error
+{
+  fn from(error: partition::Error) -> Self
+  {
+    Self::Partition(error)
+  }
+}
+
 impl From<superblock::Error> for Error
 {
   fn from(error: superblock::Error) -> Self
@@ -64,6 +265,54 @@ impl From<superblock::Error> for Error
   }
 }
 
+impl From<group_desc::Error> for Error
+{
+  fn from(error: group_desc::Error) -> Self
+  {
+    Self::GroupDesc(error)
+  }
+}
+
+impl From<inode::Error> for Error
+{
+  fn from(error: inode::Error) -> Self
+  {
+    Self::Inode(error)
+  }
+}
+
+impl From<extent::Error> for Error
+{
+  fn from(error: extent::Error) -> Self
+  {
+    Self::Extent(error)
+  }
+}
+
+impl From<dir::Error> for Error
+{
+  fn from(error: dir::Error) -> Self
+  {
+    Self::Dir(error)
+  }
+}
+
+impl From<xattr::Error> for Error
+{
+  fn from(error: xattr::Error) -> Self
+  {
+    Self::Xattr(error)
+  }
+}
+
+impl From<jbd2::Error> for Error
+{
+  fn from(error: jbd2::Error) -> Self
+  {
+    Self::Journal(error)
+  }
+}
+
 impl std::fmt::Display for Error
 {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
@@ -74,6 +323,22 @@ impl std::fmt::Display for Error
       match self {
         Self::IO(error) => format!("An IO error occurred: {}", error),
         Self::Superblock(err) => err.to_string(),
+        Self::GroupDesc(err) => err.to_string(),
+        Self::Inode(err) => err.to_string(),
+        Self::Extent(err) => err.to_string(),
+        Self::Dir(err) => err.to_string(),
+        Self::Xattr(err) => err.to_string(),
+        Self::Journal(err) => err.to_string(),
+        Self::Partition(err) => err.to_string(),
+        Self::InvalidInode(ino) => format!("Inode number {} is out of range.", ino),
+        Self::InvalidGroup(group) => format!("Block group {} is out of range.", group),
+        Self::NotFound(name) => format!("No such file or directory: {}", name),
+        Self::NotADirectory(name) => format!("Cannot look up {} in something that is not a directory.", name),
+        Self::SymlinkLoop => String::from("Too many levels of symbolic links."),
+        Self::NotASymlink => String::from("The inode is not a symbolic link."),
+        Self::SymlinkTooLong(size) => format!("Symbolic link target of {} bytes is longer than a block.", size),
+        Self::EncryptedSymlink => String::from("Cannot follow an encrypted symbolic link."),
+        Self::NoBackup(group) => format!("No backup superblock was found in group {}.", group),
       }
     )
   }
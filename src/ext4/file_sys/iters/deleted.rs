@@ -0,0 +1,185 @@
+use crate::ext4::{
+  extent::ExtentHeader,
+  file_sys::{Bitmap, Error},
+  group_desc::Flags as GroupFlags,
+  inode::Flags,
+  Extent, FileSystem, Inode,
+};
+use std::io::{self, SeekFrom};
+
+/// Inode that looks deleted, along with what is left of its data.
+#[derive(Debug, Clone)]
+pub struct DeletedInode
+{
+  /// Inode number.
+  pub ino: u32,
+  pub inode: Inode,
+  /// Whether the inode bitmap still marks the slot as used, as it does for orphans that were
+  /// unlinked while open.
+  pub in_use: bool,
+  /// Whether the inode bitmap `in_use` comes from passes its checksum, or has none to check.
+  pub bitmap_valid: bool,
+  /// Extents that still point inside the filesystem. Deletion usually clears the block pointers or
+  /// the extent tree, in which case this is empty.
+  pub extents: Vec<Extent>,
+  /// Indirect blocks or extent tree blocks that could not be read, or no longer hold an extent
+  /// node. What they mapped is missing from `extents`.
+  pub unreadable: Vec<u64>,
+}
+
+impl DeletedInode
+{
+  /// Whether any of the file's contents can still be located.
+  pub fn is_recoverable(&self) -> bool
+  {
+    !self.extents.is_empty() || self.is_in_inode()
+  }
+
+  /// Whether the contents are kept in the inode itself, as inline data or as the target of a fast
+  /// symbolic link.
+  pub fn is_in_inode(&self) -> bool
+  {
+    (self.inode.flags.contains(Flags::INLINE) && self.inode.size != 0) || self.inode.is_fast_symlink()
+  }
+}
+
+/// Walks the inode tables of every group and yields the inodes that have a deletion time, or no
+/// links while still having a mode. Groups whose inode table was never initialized are skipped. A
+/// group that cannot be read, or an inode whose mapping cannot be walked at all, yields an error
+/// and the scan goes on with the next one.
+pub struct DeletedInodeIter<'fs, R>
+{
+  fs: &'fs mut FileSystem<R>,
+  groups: u32,
+  group: u32,
+  table: Vec<u8>,
+  bitmap: Option<Bitmap>,
+  index: u32,
+}
+
+impl<'fs, R> DeletedInodeIter<'fs, R>
+where
+  R: io::Read + io::Seek,
+{
+  pub fn new(fs: &'fs mut FileSystem<R>) -> Self
+  {
+    let groups: u32 = fs.sb.get_group_count();
+    Self {
+      fs,
+      groups,
+      group: 0,
+      table: Vec::new(),
+      bitmap: None,
+      index: 0,
+    }
+  }
+
+  fn read_group(&mut self, group: u32) -> Result<(), Error>
+  {
+    self.table.clear();
+    self.bitmap = None;
+    let desc = self.fs.read_group_desc(group)?;
+    if desc.flags.contains(GroupFlags::INODE_UNINIT) {
+      return Ok(());
+    }
+    let inode_size: u64 = self.fs.sb.get_inode_size() as u64;
+    let mut table: Vec<u8> = vec![0; (self.fs.sb.inodes_per_group as u64 * inode_size) as usize];
+    io::Seek::seek(
+      self.fs,
+      SeekFrom::Start(desc.inode_table * self.fs.sb.get_block_size() as u64),
+    )?;
+    io::Read::read_exact(&mut self.fs.inner, &mut table)?;
+    self.bitmap = Some(self.fs.read_inode_bitmap(&desc)?);
+    self.table = table;
+    Ok(())
+  }
+
+  fn check_inode(
+    &mut self,
+    ino: u32,
+    raw: &[u8],
+    in_use: bool,
+    bitmap_valid: bool,
+  ) -> Result<Option<DeletedInode>, Error>
+  {
+    let inode: Inode = self.fs.parse_inode(ino, raw)?;
+    if inode.dtime.timestamp() == 0 && (inode.links_count != 0 || inode.mode.bits() == 0) {
+      return Ok(None);
+    }
+    // A block of the mapping that cannot be read, or was reused for something else than an extent
+    // node, only costs the part of the file it maps.
+    let extent_tree: bool = inode.flags.contains(Flags::EXTENTS);
+    let mut unreadable: Vec<u64> = Vec::new();
+    let mut read = |fs: &mut FileSystem<R>, block: u64| -> Result<Option<Vec<u8>>, Error> {
+      let data: Option<Vec<u8>> = fs.read_block(block).ok().filter(|data| {
+        !extent_tree || ExtentHeader::new(&mut &data[..]).is_ok_and(|header| header.check_signature().is_ok())
+      });
+      if data.is_none() {
+        unreadable.push(block);
+      }
+      Ok(data)
+    };
+    // Whatever the pointers of a deleted inode hold may be garbage, so only what maps inside the
+    // filesystem is kept.
+    let blocks_count: u64 = self.fs.sb.get_blocks_count();
+    let extents: Vec<Extent> = self
+      .fs
+      .read_extents_with(&inode, &mut read)?
+      .into_iter()
+      .filter(|extent| extent.start != 0 && extent.start + extent.len as u64 <= blocks_count)
+      .collect();
+    Ok(Some(DeletedInode {
+      ino,
+      inode,
+      in_use,
+      bitmap_valid,
+      extents,
+      unreadable,
+    }))
+  }
+}
+
+impl<R> Iterator for DeletedInodeIter<'_, R>
+where
+  R: io::Read + io::Seek,
+{
+  type Item = Result<DeletedInode, Error>;
+
+  fn next(&mut self) -> Option<Self::Item>
+  {
+    let inode_size: usize = self.fs.sb.get_inode_size() as usize;
+    loop {
+      let start: usize = self.index as usize * inode_size;
+      if start + inode_size > self.table.len() {
+        if self.group == self.groups {
+          return None;
+        }
+        let group: u32 = self.group;
+        self.group += 1;
+        self.index = 0;
+        match self.read_group(group) {
+          Ok(()) => continue,
+          Err(err) => return Some(Err(err)),
+        }
+      }
+
+      let index: u32 = self.index;
+      self.index += 1;
+      let ino: u32 = (self.group - 1) * self.fs.sb.inodes_per_group + index + 1;
+      if ino < self.fs.sb.get_first_ino() || ino > self.fs.sb.inodes_count {
+        continue;
+      }
+      let in_use: bool = self.bitmap.as_ref().is_some_and(|bitmap| bitmap.is_set(index as u64));
+      let bitmap_valid: bool = self
+        .bitmap
+        .as_ref()
+        .is_some_and(|bitmap| bitmap.check_checksum().is_none());
+      let raw: Vec<u8> = self.table[start..start + inode_size].to_vec();
+      match self.check_inode(ino, &raw, in_use, bitmap_valid) {
+        Ok(Some(deleted)) => return Some(Ok(deleted)),
+        Ok(None) => {}
+        Err(err) => return Some(Err(err)),
+      }
+    }
+  }
+}
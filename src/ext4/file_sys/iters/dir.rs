@@ -0,0 +1,142 @@
+use crate::ext4::{
+  dir::{DirBlock, DirEntry},
+  file_sys::{Error, FileReader},
+  inode::{FileType, Flags},
+  FileSystem, Inode,
+};
+use std::io;
+
+/// Iterates over the entries of a directory's linear leaf blocks. Unused entries and the
+/// metadata_csum tail are skipped. A malformed entry yields an error and the rest of its block is
+/// skipped.
+///
+/// Inline directories have no '.' entry and keep their parent in the first 4 bytes of `i_block`,
+/// which is returned as a '..' entry before the others.
+///
+/// The checksum of each block is verified as it is read, see `get_blocks`.
+pub struct DirIter<'fs, R>
+{
+  reader: FileReader<'fs, R>,
+  inline: Option<Vec<Vec<u8>>>,
+  parent: Option<DirEntry>,
+  block: Vec<u8>,
+  offset: usize,
+  filetype: bool,
+  block_size: u32,
+  done: bool,
+  seed: Option<u32>,
+  hashed: bool,
+  blocks: Vec<DirBlock>,
+}
+
+impl<'fs, R> DirIter<'fs, R>
+where
+  R: io::Read + io::Seek,
+{
+  pub fn new(fs: &'fs mut FileSystem<R>, inode: Inode) -> Result<Self, Error>
+  {
+    let filetype: bool = fs.sb.feature_filetype();
+    let seed: Option<u32> = inode.checksum_seed;
+    let hashed: bool = inode.flags.contains(Flags::HASHED_INDEXES);
+    let block_size: u32 = fs.sb.get_block_size();
+    let (inline, parent): (Option<Vec<Vec<u8>>>, Option<DirEntry>) = match inode.get_inline_data()? {
+      Some(data) if data.len() >= 4 => {
+        let parent = DirEntry {
+          inode: u32::from_le_bytes([data[0], data[1], data[2], data[3]]),
+          rec_len: 4,
+          name_len: 2,
+          file_type: filetype.then_some(FileType::Directory),
+          name: b"..".to_vec(),
+        };
+        // The entries in `i_block` and in the extended attribute are laid out as two separate
+        // blocks, popped from the back.
+        let split: usize = data.len().min(Inode::N_BLOCKS * 4);
+        let mut blocks: Vec<Vec<u8>> = vec![data[4..split].to_vec()];
+        if data.len() > split {
+          blocks.insert(0, data[split..].to_vec());
+        }
+        (Some(blocks), Some(parent))
+      }
+      Some(_) => (Some(Vec::new()), None),
+      None => (None, None),
+    };
+    Ok(Self {
+      reader: fs.open_inode(inode)?,
+      inline,
+      parent,
+      block: Vec::new(),
+      offset: 0,
+      filetype,
+      block_size,
+      done: false,
+      seed,
+      hashed,
+      blocks: Vec::new(),
+    })
+  }
+
+  /// Returns the blocks read so far, along with how they fared against their checksum. Inline
+  /// directories have none.
+  pub fn get_blocks(&self) -> &[DirBlock]
+  {
+    &self.blocks
+  }
+
+  fn read_next_block(&mut self) -> io::Result<bool>
+  {
+    if let Some(blocks) = &mut self.inline {
+      self.block = blocks.pop().unwrap_or_default();
+      self.offset = 0;
+      return Ok(!self.block.is_empty());
+    }
+    self.block.resize(self.block_size as usize, 0);
+    let mut len: usize = 0;
+    while len < self.block.len() {
+      match io::Read::read(&mut self.reader, &mut self.block[len..])? {
+        0 => break,
+        n => len += n,
+      }
+    }
+    self.block.truncate(len);
+    self.offset = 0;
+    if len != 0 {
+      let index: u32 = self.blocks.len() as u32;
+      self
+        .blocks
+        .push(DirBlock::new(&self.block, index, self.seed, self.hashed));
+    }
+    Ok(len != 0)
+  }
+}
+
+impl<R> Iterator for DirIter<'_, R>
+where
+  R: io::Read + io::Seek,
+{
+  type Item = Result<DirEntry, Error>;
+
+  fn next(&mut self) -> Option<Self::Item>
+  {
+    if let Some(parent) = self.parent.take() {
+      return Some(Ok(parent));
+    }
+    while !self.done {
+      if self.offset >= self.block.len() {
+        match self.read_next_block() {
+          Ok(true) => {}
+          Ok(false) => self.done = true,
+          Err(err) => {
+            self.done = true;
+            return Some(Err(err.into()));
+          }
+        }
+        continue;
+      }
+
+      if let Some(entry) = DirEntry::next_in_block(&self.block, &mut self.offset, self.filetype, self.block_size) {
+        return Some(entry.map_err(Error::from));
+      }
+    }
+    None
+  }
+}
@@ -1,3 +1,7 @@
+mod deleted;
+mod dir;
 mod group_desc;
 
+pub use deleted::{DeletedInode, DeletedInodeIter};
+pub use dir::DirIter;
 pub use group_desc::GroupDescIter;
@@ -1,4 +1,13 @@
+mod backup;
+pub mod bitmap;
+mod file_reader;
 mod file_system;
 pub mod iters;
+mod path;
+mod scan;
 
+pub use backup::BackupSuperblock;
+pub use bitmap::Bitmap;
+pub use file_reader::FileReader;
 pub use file_system::{Error, FileSystem};
+pub use scan::{ScannedFileSystem, SuperblockHit};
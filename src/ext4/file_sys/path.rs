@@ -0,0 +1,98 @@
+use super::{Error, FileSystem};
+use crate::ext4::{
+  inode::{FileType, Symlink},
+  Inode,
+};
+use std::io;
+
+impl<R> FileSystem<R>
+where
+  R: io::Read + io::Seek,
+{
+  /// Symbolic links followed during a single lookup before giving up, as in Linux.
+  pub const MAX_SYMLINKS: u32 = 40;
+
+  /// Resolves a path to its inode number and inode. Relative paths start at the root directory.
+  pub fn lookup<P>(&mut self, path: P) -> Result<(u32, Inode), Error>
+  where
+    P: AsRef<[u8]>,
+  {
+    self.lookup_from(Inode::ROOT_INO as u32, path)
+  }
+
+  /// Resolves a path like `lookup`, except that a symbolic link in the last component is not
+  /// followed, as with lstat.
+  pub fn lookup_nofollow<P>(&mut self, path: P) -> Result<(u32, Inode), Error>
+  where
+    P: AsRef<[u8]>,
+  {
+    self.resolve(Inode::ROOT_INO as u32, path.as_ref(), false)
+  }
+
+  /// Resolves a path to its inode number and inode. Relative paths start at the directory with
+  /// the given inode number, absolute ones at the root directory. Symbolic links are followed,
+  /// including the last component.
+  pub fn lookup_from<P>(&mut self, dir: u32, path: P) -> Result<(u32, Inode), Error>
+  where
+    P: AsRef<[u8]>,
+  {
+    self.resolve(dir, path.as_ref(), true)
+  }
+
+  fn resolve(&mut self, dir: u32, path: &[u8], follow_last: bool) -> Result<(u32, Inode), Error>
+  {
+    let mut ino: u32 = if path.first() == Some(&b'/') {
+      Inode::ROOT_INO as u32
+    } else {
+      dir
+    };
+    let mut inode: Inode = self.read_inode(ino)?;
+
+    // Components still to be resolved, with the next one at the end.
+    let mut pending: Vec<Vec<u8>> = Vec::new();
+    push_components(&mut pending, path);
+    let mut links: u32 = 0;
+    while let Some(name) = pending.pop() {
+      if FileType::from_mode(inode.mode) != FileType::Directory {
+        return Err(Error::NotADirectory(String::from_utf8_lossy(&name).into_owned()));
+      }
+      let entry = match self.find_dir_entry(&inode, &name)? {
+        Some(entry) => entry,
+        None => return Err(Error::NotFound(String::from_utf8_lossy(&name).into_owned())),
+      };
+      let parent: u32 = ino;
+      ino = entry.inode;
+      inode = self.read_inode(ino)?;
+
+      if FileType::from_mode(inode.mode) == FileType::SymbolicLink && (follow_last || !pending.is_empty()) {
+        links += 1;
+        if links > Self::MAX_SYMLINKS {
+          return Err(Error::SymlinkLoop);
+        }
+        let target: Vec<u8> = match self.read_symlink(&inode)? {
+          Symlink::Target(target) => target,
+          Symlink::Encrypted(_) => return Err(Error::EncryptedSymlink),
+        };
+        ino = if target.first() == Some(&b'/') {
+          Inode::ROOT_INO as u32
+        } else {
+          parent
+        };
+        inode = self.read_inode(ino)?;
+        push_components(&mut pending, &target);
+      }
+    }
+    Ok((ino, inode))
+  }
+}
+
+fn push_components(pending: &mut Vec<Vec<u8>>, path: &[u8])
+{
+  pending.extend(
+    path
+      .split(|&c| c == b'/')
+      .filter(|name| !name.is_empty() && *name != b".")
+      .rev()
+      .map(|name| name.to_vec()),
+  );
+}
@@ -0,0 +1,140 @@
+use super::{Error, FileSystem};
+use crate::ext4::Superblock;
+use std::io::{self, SeekFrom};
+
+/// Superblock found while scanning an image.
+#[derive(Debug)]
+pub struct SuperblockHit
+{
+  /// Byte offset of the superblock in the image.
+  pub offset: u64,
+  /// Block group the superblock belongs to. Superblocks only record the low 16 bits of it, so the
+  /// rest is worked out from where the hit lies.
+  pub group: u32,
+  pub sb: Superblock,
+}
+
+/// Filesystem pieced together from the superblocks that agree on where it starts and on its UUID.
+#[derive(Debug)]
+pub struct ScannedFileSystem
+{
+  /// Byte offset of the start of the filesystem in the image, which is what `FileSystem::new`
+  /// takes.
+  pub offset: u64,
+  /// Whether the filesystem claims more blocks than the image has room for past its start.
+  pub truncated: bool,
+  /// Superblocks of the filesystem, ordered by offset.
+  pub hits: Vec<SuperblockHit>,
+}
+
+impl ScannedFileSystem
+{
+  /// Whether any of the superblocks passes its checksum, or has none to check.
+  pub fn has_valid_superblock(&self) -> bool
+  {
+    self.hits.iter().any(|hit| hit.sb.check_checksum().is_none())
+  }
+}
+
+impl<R> FileSystem<R>
+where
+  R: io::Read + io::Seek,
+{
+  /// Size of the steps the image is scanned in. Filesystems start on a sector boundary, and every
+  /// copy of the superblock then does too.
+  pub const SCAN_STEP: u64 = 512;
+  /// Amount of the image read at once while scanning.
+  const SCAN_CHUNK: usize = 1 << 20;
+
+  /// Slides through the whole image looking for the superblock magic number, and sorts the
+  /// superblocks found into the filesystems they belong to. A hit is dropped unless its block size,
+  /// group sizes and group number make sense, and unless they place the start of its filesystem
+  /// inside the image. Filesystems with a superblock whose checksum matches are listed first.
+  pub fn scan_superblocks(inner: &mut R) -> Result<Vec<ScannedFileSystem>, Error>
+  {
+    let len: u64 = inner.seek(SeekFrom::End(0))?;
+    let mut found: Vec<ScannedFileSystem> = Vec::new();
+    let mut chunk: Vec<u8> = vec![0; Self::SCAN_CHUNK];
+    let mut pos: u64 = 0;
+    while pos < len {
+      let size: usize = (len - pos).min(Self::SCAN_CHUNK as u64) as usize;
+      inner.seek(SeekFrom::Start(pos))?;
+      inner.read_exact(&mut chunk[..size])?;
+
+      let hits: Vec<u64> = (0..size)
+        .step_by(Self::SCAN_STEP as usize)
+        .filter(|&at| {
+          chunk
+            .get(at + Superblock::MAGIC_OFFSET..at + Superblock::MAGIC_OFFSET + 2)
+            .is_some_and(|magic| u16::from_le_bytes([magic[0], magic[1]]) == Superblock::MAGIC_SIGNATURE)
+        })
+        .map(|at| pos + at as u64)
+        .collect();
+      for offset in hits {
+        inner.seek(SeekFrom::Start(offset))?;
+        let sb: Superblock = match Superblock::new(inner) {
+          Ok(sb) => sb,
+          Err(_) => continue,
+        };
+        // A group number past 16 bits wraps around, so a hit is attached to a filesystem already
+        // found if any of the groups it may belong to places it there, and otherwise starts a new
+        // one from the lowest of them.
+        let starts: Vec<(u32, u64)> = Self::get_scanned_starts(&sb, offset);
+        let existing = starts.iter().find_map(|&(group, start)| {
+          found
+            .iter()
+            .position(|fs| fs.offset == start && fs.hits[0].sb.uuid == sb.uuid)
+            .map(|index| (index, group))
+        });
+        match (existing, starts.first()) {
+          (Some((index, group)), _) => found[index].hits.push(SuperblockHit { offset, group, sb }),
+          (None, Some(&(group, start))) => found.push(ScannedFileSystem {
+            offset: start,
+            // The size was checked to fit, and the start lies before the hit.
+            truncated: sb.get_blocks_count() * sb.get_block_size() as u64 > len - start,
+            hits: vec![SuperblockHit { offset, group, sb }],
+          }),
+          (None, None) => {}
+        }
+      }
+      pos += size as u64;
+    }
+    // Filesystems none of whose superblocks have a valid checksum are the least likely to be real.
+    found.sort_by_key(|fs| (!fs.has_valid_superblock(), fs.offset));
+    Ok(found)
+  }
+
+  /// Sanity checks a superblock found at the given offset, and returns the groups it may belong
+  /// to along with where its filesystem starts for each of them. A filesystem too large for any
+  /// image to hold is taken for a stray match of the magic number. They are the groups with a copy
+  /// of the superblock whose low 16 bits match the one it records, lowest first.
+  fn get_scanned_starts(sb: &Superblock, offset: u64) -> Vec<(u32, u64)>
+  {
+    if sb.log_block_size > Superblock::MAX_BLOCK_LOG_SIZE - Superblock::MIN_BLOCK_LOG_SIZE {
+      return Vec::new();
+    }
+    // Each group has a single block for each of its bitmaps.
+    let bits_per_block: u32 = sb.get_block_size() * 8;
+    if sb.inodes_per_group == 0
+      || sb.inodes_per_group > bits_per_block
+      || sb.blocks_per_group == 0
+      || sb.clusters_per_group > bits_per_block
+      || sb.get_blocks_count() == 0
+      || sb.get_blocks_count().checked_mul(sb.get_block_size() as u64).is_none()
+    {
+      return Vec::new();
+    }
+    (sb.block_group_nr as u32..sb.get_group_count().max(1))
+      .step_by(0x10000)
+      .filter(|&group| sb.has_backup(group))
+      .filter_map(|group| {
+        let from_start: u64 = if group == 0 {
+          Self::START_OFFSET
+        } else {
+          (sb.first_data_block as u64 + group as u64 * sb.blocks_per_group as u64) * sb.get_block_size() as u64
+        };
+        offset.checked_sub(from_start).map(|start| (group, start))
+      })
+      .collect()
+  }
+}
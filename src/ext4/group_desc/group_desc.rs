@@ -1,5 +1,8 @@
 use super::{Flags, GroupDescRaw32, GroupDescRaw64};
-use crate::concat_lo_hi;
+use crate::{
+  concat_lo_hi, crc,
+  ext4::{ChecksumError, Superblock},
+};
 use std::io;
 
 #[derive(Debug)]
@@ -35,10 +38,15 @@ pub struct GroupDesc
   /// field in bg_desc is skipped when calculating crc16 checksum, and set to
   /// zero if crc32c checksum is used.
   pub checksum: u16,
+  /// Checksum computed over the descriptor when it was read, if the filesystem has one.
+  pub calculated_checksum: Option<u16>,
 }
 
 impl GroupDesc
 {
+  /// Offset of `bg_checksum`, which the checksum skips.
+  pub const CHECKSUM_OFFSET: usize = 30;
+
   pub const RAW_WIDTH32: usize = GroupDescRaw32::WIDTH;
   pub const RAW_WIDTH64: usize = GroupDescRaw64::WIDTH;
 
@@ -56,6 +64,40 @@ impl GroupDesc
       Ok(GroupDescRaw32::from(&block).into())
     }
   }
+
+  /// Computes the checksum of the raw descriptor of the given group, with either crc16 for
+  /// gdt_csum or the low 16 bits of crc32c for metadata_csum. Returns `None` if the filesystem
+  /// has neither feature.
+  pub fn calculate_checksum(sb: &Superblock, group: u32, raw: &[u8]) -> Option<u16>
+  {
+    let tail: &[u8] = raw.get(Self::CHECKSUM_OFFSET + 2..).unwrap_or(&[]);
+    if sb.feature_metadata_csum() {
+      let mut csum: u32 = crc::crc32c(sb.get_checksum_seed(), &group.to_le_bytes());
+      csum = crc::crc32c(csum, &raw[..Self::CHECKSUM_OFFSET]);
+      csum = crc::crc32c(csum, &[0; 2]);
+      csum = crc::crc32c(csum, tail);
+      Some((csum & 0xFFFF) as u16)
+    } else if sb.feature_gdt_csum() {
+      let mut csum: u16 = crc::crc16(!0, &sb.uuid.to_bytes());
+      csum = crc::crc16(csum, &group.to_le_bytes());
+      csum = crc::crc16(csum, &raw[..Self::CHECKSUM_OFFSET]);
+      csum = crc::crc16(csum, tail);
+      Some(csum)
+    } else {
+      None
+    }
+  }
+
+  /// Compares the checksum with the one computed when the descriptor was read.
+  pub fn check_checksum(&self) -> Option<ChecksumError>
+  {
+    match self.calculated_checksum {
+      Some(calculated) if calculated != self.checksum => {
+        Some(ChecksumError::Mismatch(self.checksum as u32, calculated as u32))
+      }
+      _ => None,
+    }
+  }
 }
 
 impl From<GroupDescRaw32> for GroupDesc
@@ -75,6 +117,7 @@ impl From<GroupDescRaw32> for GroupDesc
       inode_bitmap_csum: raw.bg_inode_bitmap_csum as u32,
       itable_unused: raw.bg_itable_unused as u32,
       checksum: raw.bg_checksum,
+      calculated_checksum: None,
     }
   }
 }
@@ -96,6 +139,7 @@ impl From<GroupDescRaw64> for GroupDesc
       inode_bitmap_csum: concat_lo_hi!(u32, raw.bg_inode_bitmap_csum_lo, raw.bg_inode_bitmap_csum_hi),
       itable_unused: concat_lo_hi!(u32, raw.bg_itable_unused_lo, raw.bg_itable_unused_hi),
       checksum: raw.bg_checksum,
+      calculated_checksum: None,
     }
   }
 }
@@ -1,6 +1,6 @@
 use super::Mode;
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum FileType
 {
   Fifo,
@@ -0,0 +1,40 @@
+use super::{Flags, Inode};
+use crate::ext4::xattr::{Error, NameIndex, XattrEntry};
+
+impl Inode
+{
+  /// Reads the extended attribute entries stored in the inode itself. An inode without room for
+  /// them, or whose space does not start with the magic number, has none.
+  pub fn get_inode_xattrs(&self) -> Result<Vec<XattrEntry>, Error>
+  {
+    match self.xattr_space.get(..4) {
+      Some(magic) if u32::from_le_bytes([magic[0], magic[1], magic[2], magic[3]]) == XattrEntry::MAGIC_SIGNATURE => {
+        XattrEntry::read_list(&self.xattr_space[4..])
+      }
+      _ => Ok(Vec::new()),
+    }
+  }
+
+  /// Returns the contents of an inode with the inline data flag. The first 60 bytes live in
+  /// `i_block` and the rest in the "system.data" extended attribute. Anything missing past the
+  /// returned bytes is read as zeroes.
+  pub fn get_inline_data(&self) -> Result<Option<Vec<u8>>, Error>
+  {
+    if !self.flags.contains(Flags::INLINE) {
+      return Ok(None);
+    }
+    let mut data: Vec<u8> = self.get_block_bytes().to_vec();
+    if self.size > data.len() as u64 {
+      let entry: Option<XattrEntry> = self
+        .get_inode_xattrs()?
+        .into_iter()
+        .find(|entry| entry.name_index == NameIndex::System && entry.name == XattrEntry::INLINE_DATA_NAME);
+      if let Some(entry) = entry {
+        // Values in the inode are counted from the first entry, right after the magic number.
+        data.extend_from_slice(entry.get_value(&self.xattr_space[4..])?);
+      }
+    }
+    data.truncate(self.size.min(usize::MAX as u64) as usize);
+    Ok(Some(data))
+  }
+}
@@ -1,9 +1,15 @@
 use super::{Flags, InodeRaw, InodeRawLarge, Mode, Osd1, Osd2};
-use crate::{concat_lo_hi, ext4::superblock::Creator};
+use crate::{
+  concat_lo_hi, crc,
+  ext4::{
+    superblock::{Creator, Superblock},
+    ChecksumError,
+  },
+};
 use chrono::{DateTime, TimeZone, Utc};
 use std::io;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Inode
 {
   /// File mode.
@@ -49,7 +55,7 @@ pub struct Inode
   /// Lower 32-bits of extended attribute block. ACLs are of course one of many
   /// possible extended attributes; I think the name of this field is a result
   /// of the first use of extended attributes being for ACLs.
-  pub file_acl: u32,
+  pub file_acl: u64,
   /// (Obsolete) fragment address.
   pub obso_faddr: u32,
   /// See the table i_osd2 for more details.
@@ -72,6 +78,16 @@ pub struct Inode
   pub crtime_extra: u32,
   /// Project ID.
   pub projid: u32,
+  /// Checksum computed over the inode when it was read, if the filesystem has metadata_csum.
+  pub calculated_checksum: Option<u32>,
+  /// Seed of the checksums of the extent tree and directory blocks the inode owns, if the
+  /// filesystem has metadata_csum. It depends on the inode number, so it is only known for inodes
+  /// read through the filesystem.
+  pub checksum_seed: Option<u32>,
+  /// Extended attribute space past the extra fields, starting with its magic
+  /// number. Empty unless the inode was read through `FileSystem::read_inode`
+  /// and has room for it.
+  pub xattr_space: Vec<u8>,
 }
 
 impl Inode
@@ -107,6 +123,10 @@ impl Inode
   pub const LINK_MAX: u16 = 65000;
 
   pub const GOOD_OLD_INODE_SIZE: u16 = 128;
+  /// Offset of `l_i_checksum_lo`, inside `i_osd2`.
+  pub const CHECKSUM_LO_OFFSET: usize = 124;
+  /// Offset of `i_checksum_hi`.
+  pub const CHECKSUM_HI_OFFSET: usize = 130;
 
   // Constants relative to the data blocks.
   pub const NDIR_BLOCKS: usize = 12;
@@ -130,9 +150,86 @@ impl Inode
     }
   }
 
+  /// The `i_block` array in its on-disk byte order.
+  pub fn get_block_bytes(&self) -> [u8; Self::N_BLOCKS * 4]
+  {
+    let mut bytes: [u8; Self::N_BLOCKS * 4] = [0; Self::N_BLOCKS * 4];
+    for (chunk, block) in bytes.chunks_exact_mut(4).zip(self.block.iter()) {
+      chunk.copy_from_slice(&block.to_le_bytes());
+    }
+    bytes
+  }
+
+  /// Computes the metadata_csum checksum of the raw bytes of the inode with the given number,
+  /// which span the whole inode size. It covers the inode number and generation, then the inode
+  /// with its checksum fields zeroed. Only the low 16 bits are kept when the inode has no room for
+  /// `i_checksum_hi`. Returns `None` if the filesystem has no metadata_csum.
+  pub fn calculate_checksum(sb: &Superblock, ino: u32, raw: &[u8]) -> Option<u32>
+  {
+    if !sb.feature_metadata_csum() || raw.len() < Self::GOOD_OLD_INODE_SIZE as usize {
+      return None;
+    }
+    let base: usize = Self::GOOD_OLD_INODE_SIZE as usize;
+    let generation: u32 = u32::from_le_bytes([raw[100], raw[101], raw[102], raw[103]]);
+    let mut csum: u32 = Self::calculate_checksum_seed(sb, ino, generation);
+    csum = crc::crc32c(csum, &raw[..Self::CHECKSUM_LO_OFFSET]);
+    csum = crc::crc32c(csum, &[0; 2]);
+    csum = crc::crc32c(csum, &raw[Self::CHECKSUM_LO_OFFSET + 2..base]);
+    if raw.len() == base {
+      return Some(csum & 0xFFFF);
+    }
+
+    let extra_isize: usize = u16::from_le_bytes([raw[base], raw[base + 1]]) as usize;
+    let has_hi: bool = base + extra_isize >= Self::CHECKSUM_HI_OFFSET + 2 && raw.len() >= Self::CHECKSUM_HI_OFFSET + 2;
+    let tail: usize = if has_hi {
+      csum = crc::crc32c(csum, &raw[base..Self::CHECKSUM_HI_OFFSET]);
+      csum = crc::crc32c(csum, &[0; 2]);
+      Self::CHECKSUM_HI_OFFSET + 2
+    } else {
+      base
+    };
+    csum = crc::crc32c(csum, &raw[tail..]);
+    Some(if has_hi { csum } else { csum & 0xFFFF })
+  }
+
+  /// Computes the seed shared by the checksums of an inode and of the blocks it owns, from the
+  /// filesystem seed, the inode number and its generation.
+  pub fn calculate_checksum_seed(sb: &Superblock, ino: u32, generation: u32) -> u32
+  {
+    let csum: u32 = crc::crc32c(sb.get_checksum_seed(), &ino.to_le_bytes());
+    crc::crc32c(csum, &generation.to_le_bytes())
+  }
+
+  /// Compares the checksum with the one computed when the inode was read.
+  pub fn check_checksum(&self) -> Option<ChecksumError>
+  {
+    match self.calculated_checksum {
+      Some(calculated) if calculated != self.checksum => Some(ChecksumError::Mismatch(self.checksum, calculated)),
+      _ => None,
+    }
+  }
+
+  /// Number of 512-byte sectors the file consumes on disk.
+  pub fn get_blocks_count(&self, sb: &Superblock) -> u64
+  {
+    if !sb.feature_huge_file() {
+      return self.blocks_lo as u64;
+    }
+    let blocks_high = match self.osd2 {
+      Osd2::Linux { blocks_high, .. } => blocks_high,
+      _ => 0,
+    };
+    let blocks = concat_lo_hi!(u64, self.blocks_lo, blocks_high);
+    if self.flags.contains(Flags::HUGE_FILE) {
+      blocks * (sb.get_block_size() as u64 / 512)
+    } else {
+      blocks
+    }
+  }
+
   fn from_raw(raw: InodeRaw, os: &Creator) -> Self
   {
-    let osd2 = Osd2::from_raw(raw.i_osd2, &os);
+    let osd2 = Osd2::from_raw(raw.i_osd2, os);
     Self {
       mode: Mode::from_raw(raw.i_mode),
       uid: concat_lo_hi!(
@@ -161,11 +258,11 @@ impl Inode
       links_count: raw.i_links_count,
       blocks_lo: raw.i_blocks_lo,
       flags: Flags::from_raw(raw.i_flags),
-      osd1: Osd1::from_raw(raw.i_osd1, &os),
+      osd1: Osd1::from_raw(raw.i_osd1, os),
       block: raw.i_block,
       generation: raw.i_generation,
       file_acl: concat_lo_hi!(
-        u32,
+        u64,
         raw.i_file_acl_lo,
         match osd2 {
           Osd2::Linux { file_acl_high, .. } => file_acl_high,
@@ -176,19 +273,25 @@ impl Inode
       obso_faddr: raw.i_obso_faddr,
       osd2,
       extra_isize: 0,
-      checksum: 0,
+      checksum: match osd2 {
+        Osd2::Linux { checksum_lo, .. } => checksum_lo as u32,
+        _ => 0,
+      },
       ctime_extra: 0,
       mtime_extra: 0,
       atime_extra: 0,
       crtime: Utc.timestamp(0, 0),
       crtime_extra: 0,
       projid: 0,
+      calculated_checksum: None,
+      checksum_seed: None,
+      xattr_space: Vec::new(),
     }
   }
 
   fn from_raw_large(raw: InodeRawLarge, os: &Creator) -> Self
   {
-    let osd2 = Osd2::from_raw(raw.i_osd2, &os);
+    let osd2 = Osd2::from_raw(raw.i_osd2, os);
     Self {
       mode: Mode::from_raw(raw.i_mode),
       uid: concat_lo_hi!(
@@ -217,11 +320,11 @@ impl Inode
       links_count: raw.i_links_count,
       blocks_lo: raw.i_blocks_lo,
       flags: Flags::from_raw(raw.i_flags),
-      osd1: Osd1::from_raw(raw.i_osd1, &os),
+      osd1: Osd1::from_raw(raw.i_osd1, os),
       block: raw.i_block,
       generation: raw.i_generation,
       file_acl: concat_lo_hi!(
-        u32,
+        u64,
         raw.i_file_acl_lo,
         match osd2 {
           Osd2::Linux { file_acl_high, .. } => file_acl_high,
@@ -242,6 +345,9 @@ impl Inode
       crtime: Utc.timestamp(raw.i_crtime as i64, 0),
       crtime_extra: raw.i_crtime_extra,
       projid: raw.i_projid,
+      calculated_checksum: None,
+      checksum_seed: None,
+      xattr_space: Vec::new(),
     }
   }
 }
@@ -1,16 +1,19 @@
 mod file_type;
 mod flags;
+mod inline_data;
 mod inode;
 mod mode;
 mod osd1;
 mod osd2;
 mod raw;
+mod symlink;
 
 pub use file_type::FileType;
 pub use flags::Flags;
-pub use inode::Inode;
+pub use inode::{Error, Inode};
 pub use mode::Mode;
 pub use osd1::Osd1;
 pub use osd2::Osd2;
 pub(crate) use osd2::Osd2Raw;
 pub(crate) use raw::{InodeRaw, InodeRawLarge};
+pub use symlink::Symlink;
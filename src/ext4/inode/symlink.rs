@@ -0,0 +1,105 @@
+use super::{Flags, Inode, Mode};
+use crate::ext4::{
+  file_sys::{Error, FileReader},
+  Extent, FileSystem,
+};
+use std::io::{self, Read};
+
+/// Target of a symbolic link.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Symlink
+{
+  /// The path the link points to.
+  Target(Vec<u8>),
+  /// The encrypted path of a link in an encrypted directory. Without the key it can only be
+  /// copied as is.
+  Encrypted(Vec<u8>),
+}
+
+impl Symlink
+{
+  fn new(data: Vec<u8>, encrypted: bool) -> Self
+  {
+    if !encrypted {
+      return Self::Target(data);
+    }
+    // Encrypted targets are stored as a 16-bit length followed by the ciphertext.
+    match data.get(..2).map(|len| u16::from_le_bytes([len[0], len[1]]) as usize) {
+      Some(len) if len + 2 <= data.len() => Self::Encrypted(data[2..2 + len].to_vec()),
+      _ => Self::Encrypted(data),
+    }
+  }
+}
+
+impl std::fmt::Display for Symlink
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    match self {
+      Self::Target(target) => write!(f, "{}", String::from_utf8_lossy(target)),
+      Self::Encrypted(ciphertext) => {
+        write!(f, "<encrypted: ")?;
+        for byte in ciphertext {
+          write!(f, "{:02x}", byte)?;
+        }
+        write!(f, ">")
+      }
+    }
+  }
+}
+
+impl Inode
+{
+  /// Whether this is a symbolic link whose target is short enough to be stored in `i_block`
+  /// instead of in a data block.
+  pub fn is_fast_symlink(&self) -> bool
+  {
+    self.mode.file_type_flags() == Mode::SYMLINK
+      && self.size != 0
+      && self.size < (Self::N_BLOCKS * 4) as u64
+      && !self.flags.intersects(Flags::EXTENTS | Flags::INLINE)
+  }
+
+  /// Returns the target of a fast symbolic link, which needs nothing but the inode itself. Slow
+  /// links are read through [`FileSystem::read_symlink`].
+  pub fn get_fast_symlink(&self) -> Option<Symlink>
+  {
+    if self.is_fast_symlink() {
+      let target: Vec<u8> = self.get_block_bytes()[..self.size as usize].to_vec();
+      Some(Symlink::new(target, self.flags.contains(Flags::ENCRYPTED)))
+    } else {
+      None
+    }
+  }
+}
+
+impl<R> FileSystem<R>
+where
+  R: io::Read + io::Seek,
+{
+  /// Reads the target of a symbolic link, whether it is kept in `i_block` or in data blocks. A
+  /// target cannot be longer than a block, so a larger size is taken for a corrupted inode.
+  pub fn read_symlink(&mut self, inode: &Inode) -> Result<Symlink, Error>
+  {
+    let extents: Vec<Extent> = self.read_extents(inode)?;
+    self.read_symlink_from_extents(inode, extents)
+  }
+
+  /// Same as `read_symlink`, but a slow link is read through the given extents, such as what is
+  /// left of a deleted link's.
+  pub fn read_symlink_from_extents(&mut self, inode: &Inode, extents: Vec<Extent>) -> Result<Symlink, Error>
+  {
+    if inode.mode.file_type_flags() != Mode::SYMLINK {
+      return Err(Error::NotASymlink);
+    }
+    if let Some(symlink) = inode.get_fast_symlink() {
+      return Ok(symlink);
+    }
+    if inode.size > self.sb.get_block_size() as u64 {
+      return Err(Error::SymlinkTooLong(inode.size));
+    }
+    let mut target: Vec<u8> = Vec::with_capacity(inode.size as usize);
+    FileReader::from_extents(self, inode.clone(), extents)?.read_to_end(&mut target)?;
+    Ok(Symlink::new(target, inode.flags.contains(Flags::ENCRYPTED)))
+  }
+}
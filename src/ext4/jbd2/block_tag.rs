@@ -0,0 +1,107 @@
+use super::{JournalBlockTag3Raw, JournalBlockTagRaw, JournalHeader, JournalSuperblock};
+use crate::{
+  concat_lo_hi,
+  uuid::{Uuid, UuidRaw},
+};
+use bitflags::bitflags;
+
+bitflags! {
+  pub struct TagFlags: u32
+  {
+    /// On-disk block is escaped. The first four bytes of the data block just
+    /// happened to match the jbd2 magic number.
+    const ESCAPE = 0x1;
+    /// This block has the same UUID as previous, therefore the UUID field is
+    /// omitted.
+    const SAME_UUID = 0x2;
+    /// The data block was deleted by the transaction. (Not used?)
+    const DELETED = 0x4;
+    /// This is the last tag in this descriptor block.
+    const LAST_TAG = 0x8;
+  }
+}
+
+impl TagFlags
+{
+  pub fn from_raw(raw: u32) -> Self
+  {
+    unsafe { Self::from_bits_unchecked(raw) }
+  }
+}
+
+/// Entry of a descriptor block, which tells where the data block logged after it belongs.
+#[derive(Debug)]
+pub struct BlockTag
+{
+  /// Location of where the corresponding data block should end up on disk.
+  pub block: u64,
+  /// Flags that go with the descriptor.
+  pub flags: TagFlags,
+  /// Checksum of the journal UUID, the sequence number, and the data block.
+  /// Only the lower 16 bits are stored without the v3 checksum format, and
+  /// nothing without checksums at all.
+  pub checksum: u32,
+  /// UUID stored after the tag, unless the tag has the same UUID as the
+  /// previous one.
+  pub uuid: Option<Uuid>,
+}
+
+impl BlockTag
+{
+  /// Reads the tags of a descriptor block, which start right after its header and go on until the
+  /// one flagged as the last or until the block runs out.
+  pub fn read_descriptor(block: &[u8], sb: &JournalSuperblock) -> Vec<Self>
+  {
+    let tag_size: usize = sb.get_tag_size();
+    let end: usize = block.len().saturating_sub(sb.get_tail_size());
+    let mut tags: Vec<Self> = Vec::new();
+    let mut offset: usize = JournalHeader::RAW_WIDTH;
+    while offset + tag_size <= end {
+      let mut tag: Self = Self::from_bytes(&block[offset..offset + tag_size], sb);
+      offset += tag_size;
+      if !tag.flags.contains(TagFlags::SAME_UUID) {
+        let mut uuid: [u8; 16] = [0; 16];
+        if let Some(bytes) = block.get(offset..offset + 16) {
+          uuid.copy_from_slice(bytes);
+        }
+        tag.uuid = Some(Uuid::from(unsafe { std::mem::transmute::<[u8; 16], UuidRaw>(uuid) }));
+        offset += 16;
+      }
+      let last: bool = tag.flags.contains(TagFlags::LAST_TAG);
+      tags.push(tag);
+      if last {
+        break;
+      }
+    }
+    tags
+  }
+
+  fn from_bytes(bytes: &[u8], sb: &JournalSuperblock) -> Self
+  {
+    let (block, high, flags, checksum): (u32, u32, u32, u32) = if sb.feature_csum_v3() {
+      let mut tag: [u8; JournalBlockTag3Raw::WIDTH] = [0; JournalBlockTag3Raw::WIDTH];
+      tag.copy_from_slice(&bytes[..JournalBlockTag3Raw::WIDTH]);
+      let raw = JournalBlockTag3Raw::from(&tag);
+      (raw.t_blocknr, raw.t_blocknr_high, raw.t_flags, raw.t_checksum)
+    } else {
+      // Tags without the 64-bit feature leave out the upper half of the block number, which then
+      // reads as zero.
+      let mut tag: [u8; JournalBlockTagRaw::WIDTH] = [0; JournalBlockTagRaw::WIDTH];
+      let len: usize = bytes.len().min(JournalBlockTagRaw::WIDTH);
+      tag[..len].copy_from_slice(&bytes[..len]);
+      let raw = JournalBlockTagRaw::from(&tag);
+      (
+        raw.t_blocknr,
+        raw.t_blocknr_high,
+        raw.t_flags as u32,
+        if sb.feature_csum_v2() { raw.t_checksum as u32 } else { 0 },
+      )
+    };
+    Self {
+      block: concat_lo_hi!(u64, block, if sb.feature_64bit() { high } else { 0 }),
+      flags: TagFlags::from_raw(flags),
+      checksum,
+      uuid: None,
+    }
+  }
+}
@@ -0,0 +1,116 @@
+use super::JournalSuperblock;
+use crate::add_to_list;
+use bitflags::bitflags;
+
+macro_rules! feature {
+  ($feature_name:ident, $feature_type:ident, $name:ident, $feature_flag:ident) => {
+    impl JournalSuperblock
+    {
+      #[inline(always)]
+      pub fn $name(&self) -> bool
+      {
+        self.$feature_name.contains($feature_type::$feature_flag)
+      }
+    }
+  };
+}
+
+bitflags! {
+  pub struct JournalFeatureCompat: u32
+  {
+    /// Journal maintains checksums on the data blocks.
+    const CHECKSUM = 0x1;
+  }
+}
+
+impl JournalFeatureCompat
+{
+  pub fn from_raw(raw: u32) -> Self
+  {
+    unsafe { Self::from_bits_unchecked(raw) }
+  }
+
+  pub fn features_list(&self) -> Vec<&str>
+  {
+    let mut output = Vec::new();
+    add_to_list!(self, output, "journal_checksum", CHECKSUM);
+    output
+  }
+}
+
+impl std::fmt::Display for JournalFeatureCompat
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    write!(f, "{}", crate::util::get_string_list(&self.features_list()))
+  }
+}
+
+bitflags! {
+  pub struct JournalFeatureIncompat: u32
+  {
+    /// Journal has block revocation records.
+    const REVOKE = 0x1;
+    /// Journal can deal with 64-bit block numbers.
+    const BIT64 = 0x2;
+    /// Journal commits asynchronously.
+    const ASYNC_COMMIT = 0x4;
+    /// This journal uses v2 of the checksum on-disk format. Each journal
+    /// metadata block gets its own checksum, and the block tags in the
+    /// descriptor table contain checksums for each of the data blocks in the
+    /// journal.
+    const CSUM_V2 = 0x8;
+    /// This journal uses v3 of the checksum on-disk format. This is the same
+    /// as v2, but the journal block tag size is fixed regardless of the size
+    /// of block numbers.
+    const CSUM_V3 = 0x10;
+    /// Journal has fast commit blocks.
+    const FAST_COMMIT = 0x20;
+  }
+}
+
+impl JournalFeatureIncompat
+{
+  pub fn from_raw(raw: u32) -> Self
+  {
+    unsafe { Self::from_bits_unchecked(raw) }
+  }
+
+  pub fn features_list(&self) -> Vec<&str>
+  {
+    let mut output = Vec::new();
+    add_to_list!(self, output, "journal_incompat_revoke", REVOKE);
+    add_to_list!(self, output, "journal_64bit", BIT64);
+    add_to_list!(self, output, "journal_async_commit", ASYNC_COMMIT);
+    add_to_list!(self, output, "journal_checksum_v2", CSUM_V2);
+    add_to_list!(self, output, "journal_checksum_v3", CSUM_V3);
+    add_to_list!(self, output, "journal_fast_commit", FAST_COMMIT);
+    output
+  }
+}
+
+impl std::fmt::Display for JournalFeatureIncompat
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    write!(f, "{}", crate::util::get_string_list(&self.features_list()))
+  }
+}
+
+feature!(feature_compat, JournalFeatureCompat, feature_checksum, CHECKSUM);
+feature!(feature_incompat, JournalFeatureIncompat, feature_revoke, REVOKE);
+feature!(feature_incompat, JournalFeatureIncompat, feature_64bit, BIT64);
+feature!(
+  feature_incompat,
+  JournalFeatureIncompat,
+  feature_async_commit,
+  ASYNC_COMMIT
+);
+feature!(feature_incompat, JournalFeatureIncompat, feature_csum_v2, CSUM_V2);
+feature!(feature_incompat, JournalFeatureIncompat, feature_csum_v3, CSUM_V3);
+feature!(
+  feature_incompat,
+  JournalFeatureIncompat,
+  feature_fast_commit,
+  FAST_COMMIT
+);
@@ -0,0 +1,193 @@
+use super::{BlockTag, BlockType, CommitHeaderRaw, Error, JournalHeader, JournalSuperblock, RevokeHeaderRaw, TagFlags};
+use crate::ext4::{file_sys, inode::Mode, Extent, FileSystem, Inode};
+use chrono::{DateTime, TimeZone, Utc};
+use std::io;
+
+/// Internal journal of a filesystem. Journal blocks are numbered from the start of the journal
+/// inode.
+#[derive(Debug)]
+pub struct Journal
+{
+  pub sb: JournalSuperblock,
+  extents: Vec<Extent>,
+}
+
+/// Copy of a filesystem block logged by a transaction.
+#[derive(Debug)]
+pub struct LoggedBlock
+{
+  /// Journal block the copy is stored in.
+  pub journal_block: u32,
+  /// Tag from the descriptor block, which tells where the copy belongs.
+  pub tag: BlockTag,
+}
+
+/// Transaction assembled from the descriptor, revoke and commit blocks that share a sequence
+/// number.
+#[derive(Debug)]
+pub struct Transaction
+{
+  /// The transaction ID.
+  pub sequence: u32,
+  /// Filesystem blocks logged by the transaction.
+  pub blocks: Vec<LoggedBlock>,
+  /// Filesystem blocks revoked by the transaction.
+  pub revoked: Vec<u64>,
+  /// Time of the commit block, if the transaction was committed.
+  pub commit_time: Option<DateTime<Utc>>,
+}
+
+impl Journal
+{
+  /// Returns the filesystem block a journal block is stored in.
+  pub fn map(&self, journal_block: u32) -> Option<u64>
+  {
+    let idx: usize = self
+      .extents
+      .partition_point(|extent| extent.block as u64 + extent.len as u64 <= journal_block as u64);
+    self.extents.get(idx).and_then(|extent| extent.map(journal_block))
+  }
+}
+
+impl<R> FileSystem<R>
+where
+  R: io::Read + io::Seek,
+{
+  /// Opens the journal stored in the journal inode and reads its superblock.
+  pub fn open_journal(&mut self) -> Result<Journal, file_sys::Error>
+  {
+    if !self.sb.feature_has_journal() || self.sb.journal_inum == 0 {
+      return Err(Error::NoJournal.into());
+    }
+    let inode: Inode = self.read_inode(self.sb.journal_inum)?;
+    if inode.mode.file_type_flags() != Mode::REGULAR {
+      return Err(Error::NoJournal.into());
+    }
+    let extents: Vec<Extent> = self.read_extents(&inode)?;
+    let first: u64 = extents
+      .first()
+      .and_then(|extent| extent.map(0))
+      .ok_or(Error::Unmapped(0))?;
+    let sb: JournalSuperblock = JournalSuperblock::new(&mut &self.read_block(first)?[..])?;
+    if sb.block_size != self.sb.get_block_size() {
+      return Err(Error::BlockSize(sb.block_size, self.sb.get_block_size()).into());
+    }
+    Ok(Journal { sb, extents })
+  }
+
+  pub fn read_journal_block(&mut self, journal: &Journal, journal_block: u32) -> Result<Vec<u8>, file_sys::Error>
+  {
+    match journal.map(journal_block) {
+      Some(block) => self.read_block(block),
+      None => Err(Error::Unmapped(journal_block).into()),
+    }
+  }
+
+  /// Reads the copy of a block logged by a transaction, with the magic number it may have been
+  /// escaped for put back.
+  pub fn read_logged_block(&mut self, journal: &Journal, logged: &LoggedBlock) -> Result<Vec<u8>, file_sys::Error>
+  {
+    let mut block: Vec<u8> = self.read_journal_block(journal, logged.journal_block)?;
+    if logged.tag.flags.contains(TagFlags::ESCAPE) {
+      block[..4].copy_from_slice(&JournalHeader::MAGIC_SIGNATURE.to_be_bytes());
+    }
+    Ok(block)
+  }
+
+  /// Walks the whole log once, starting where the superblock says it starts, and gathers the
+  /// transactions found in it ordered by sequence number. Besides the ones that still need to be
+  /// replayed, this includes older transactions that have not been overwritten yet, though some of
+  /// their blocks may have been.
+  pub fn read_transactions(&mut self, journal: &Journal) -> Result<Vec<Transaction>, file_sys::Error>
+  {
+    let first: u32 = journal.sb.first.max(1);
+    let len: u32 = journal.sb.max_len.saturating_sub(first);
+    if len == 0 {
+      return Ok(Vec::new());
+    }
+    // The log wraps around from its last block back to the first one.
+    let wrap = |journal_block: u64| first + ((journal_block - first as u64) % len as u64) as u32;
+    let start: u32 = if journal.sb.start >= first && journal.sb.start < journal.sb.max_len {
+      journal.sb.start
+    } else {
+      first
+    };
+
+    let mut transactions: Vec<Transaction> = Vec::new();
+    let mut idx: u32 = 0;
+    while idx < len {
+      let journal_block: u32 = wrap(start as u64 + idx as u64);
+      idx += 1;
+      let block: Vec<u8> = match self.read_journal_block(journal, journal_block) {
+        Ok(block) => block,
+        Err(file_sys::Error::Journal(Error::Unmapped(_))) => continue,
+        Err(err) => return Err(err),
+      };
+      let header: JournalHeader = JournalHeader::new(&mut &block[..])?;
+      if header.check_signature().is_err()
+        || !matches!(
+          header.block_type,
+          BlockType::Descriptor | BlockType::Commit | BlockType::Revoke
+        )
+      {
+        continue;
+      }
+      let transaction: &mut Transaction = match transactions
+        .iter()
+        .rposition(|transaction| transaction.sequence == header.sequence)
+      {
+        Some(pos) => &mut transactions[pos],
+        None => {
+          transactions.push(Transaction {
+            sequence: header.sequence,
+            blocks: Vec::new(),
+            revoked: Vec::new(),
+            commit_time: None,
+          });
+          transactions.last_mut().unwrap()
+        }
+      };
+
+      match header.block_type {
+        BlockType::Descriptor => {
+          // The logged blocks follow the descriptor in the order of its tags.
+          let tags: Vec<BlockTag> = BlockTag::read_descriptor(&block, &journal.sb);
+          idx += tags.len() as u32;
+          for (i, tag) in tags.into_iter().enumerate() {
+            transaction.blocks.push(LoggedBlock {
+              journal_block: wrap(journal_block as u64 + 1 + i as u64),
+              tag,
+            });
+          }
+        }
+        BlockType::Commit => {
+          let mut commit: [u8; CommitHeaderRaw::WIDTH] = [0; CommitHeaderRaw::WIDTH];
+          commit.copy_from_slice(&block[..CommitHeaderRaw::WIDTH]);
+          let raw = CommitHeaderRaw::from(&commit);
+          transaction.commit_time = Some(
+            Utc
+              .timestamp_opt(raw.h_commit_sec as i64, raw.h_commit_nsec % 1_000_000_000)
+              .single()
+              .unwrap_or_else(|| Utc.timestamp(0, 0)),
+          );
+        }
+        BlockType::Revoke => {
+          let mut revoke: [u8; RevokeHeaderRaw::WIDTH] = [0; RevokeHeaderRaw::WIDTH];
+          revoke.copy_from_slice(&block[..RevokeHeaderRaw::WIDTH]);
+          let raw = RevokeHeaderRaw::from(&revoke);
+          let end: usize = (raw.r_count as usize).min(block.len() - journal.sb.get_tail_size());
+          let width: usize = if journal.sb.feature_64bit() { 8 } else { 4 };
+          let records = block.get(RevokeHeaderRaw::WIDTH..end).unwrap_or_default();
+          transaction.revoked.extend(
+            records
+              .chunks_exact(width)
+              .map(|record| record.iter().fold(0u64, |block, &byte| block << 8 | byte as u64)),
+          );
+        }
+        _ => {}
+      }
+    }
+    transactions.sort_by_key(|transaction| transaction.sequence);
+    Ok(transactions)
+  }
+}
@@ -0,0 +1,270 @@
+use super::{JournalFeatureCompat, JournalFeatureIncompat, JournalHeaderRaw, JournalSuperblockRaw};
+use crate::uuid::{Uuid, UuidRaw};
+use std::io;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BlockType
+{
+  Descriptor,
+  Commit,
+  SuperblockV1,
+  SuperblockV2,
+  Revoke,
+  Unknown(u32),
+}
+
+impl BlockType
+{
+  const DESCRIPTOR: u32 = 1;
+  const COMMIT: u32 = 2;
+  const SUPERBLOCK_V1: u32 = 3;
+  const SUPERBLOCK_V2: u32 = 4;
+  const REVOKE: u32 = 5;
+
+  pub fn from_raw(raw: u32) -> Self
+  {
+    match raw {
+      Self::DESCRIPTOR => Self::Descriptor,
+      Self::COMMIT => Self::Commit,
+      Self::SUPERBLOCK_V1 => Self::SuperblockV1,
+      Self::SUPERBLOCK_V2 => Self::SuperblockV2,
+      Self::REVOKE => Self::Revoke,
+      _ => Self::Unknown(raw),
+    }
+  }
+}
+
+impl std::fmt::Display for BlockType
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Descriptor => "Descriptor",
+        Self::Commit => "Commit",
+        Self::SuperblockV1 => "Superblock v1",
+        Self::SuperblockV2 => "Superblock v2",
+        Self::Revoke => "Revoke",
+        Self::Unknown(raw) => return write!(f, "Unknown block type {}", raw),
+      }
+    )
+  }
+}
+
+/// Header found at the start of every journal metadata block.
+#[derive(Debug, Copy, Clone)]
+pub struct JournalHeader
+{
+  /// jbd2 magic number, 0xC03B3998.
+  pub magic: u32,
+  /// Description of what this block contains.
+  pub block_type: BlockType,
+  /// The transaction ID that goes with this block.
+  pub sequence: u32,
+}
+
+impl JournalHeader
+{
+  pub const RAW_WIDTH: usize = JournalHeaderRaw::WIDTH;
+  pub const MAGIC_SIGNATURE: u32 = 0xC03B3998;
+
+  pub fn new<R>(inner: &mut R) -> Result<Self, Error>
+  where
+    R: io::Read,
+  {
+    let mut block: [u8; Self::RAW_WIDTH] = [0; Self::RAW_WIDTH];
+    inner.read_exact(&mut block)?;
+    Ok(Self::from_raw(JournalHeaderRaw::from(&block)))
+  }
+
+  pub(crate) fn from_raw(raw: JournalHeaderRaw) -> Self
+  {
+    Self {
+      magic: raw.h_magic,
+      block_type: BlockType::from_raw(raw.h_blocktype),
+      sequence: raw.h_sequence,
+    }
+  }
+
+  pub fn check_signature(&self) -> Result<(), Error>
+  {
+    if self.magic == Self::MAGIC_SIGNATURE {
+      Ok(())
+    } else {
+      Err(Error::Signature(self.magic))
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct JournalSuperblock
+{
+  /// Common header identifying this as a superblock.
+  pub header: JournalHeader,
+  /// Journal device block size.
+  pub block_size: u32,
+  /// Total number of blocks in this journal.
+  pub max_len: u32,
+  /// First block of log information.
+  pub first: u32,
+  /// First commit ID expected in log.
+  pub sequence: u32,
+  /// Block number of the start of log. Contrary to the comments, this field
+  /// being zero does not imply that the journal is clean!
+  pub start: u32,
+  /// Error value, as set by jbd2_journal_abort().
+  pub errno: i32,
+  /// Compatible feature set. Always empty in a v1 superblock.
+  pub feature_compat: JournalFeatureCompat,
+  /// Incompatible feature set. Always empty in a v1 superblock.
+  pub feature_incompat: JournalFeatureIncompat,
+  /// Read-only compatible feature set. There aren't any of these currently.
+  pub feature_ro_compat: u32,
+  /// 128-bit uuid for journal. This is compared against the copy in the ext4
+  /// super block at mount time.
+  pub uuid: Uuid,
+  /// Number of file systems sharing this journal.
+  pub nr_users: u32,
+  /// Limit of journal blocks per transaction. (Not used?)
+  pub max_transaction: u32,
+  /// Limit of data blocks per transaction. (Not used?)
+  pub max_trans_data: u32,
+  /// Checksum algorithm used for the journal. 4 is crc32c.
+  pub checksum_type: u8,
+  /// Number of fast commit blocks in the journal.
+  pub num_fc_blks: u32,
+  /// Block number of the head (first unused block) of the journal, only
+  /// up-to-date when the journal is empty.
+  pub head: u32,
+  /// Checksum of the entire superblock, with this field set to zero.
+  pub checksum: u32,
+  /// ids of all file systems sharing the log.
+  pub users: Vec<Uuid>,
+}
+
+impl JournalSuperblock
+{
+  pub const RAW_WIDTH: usize = JournalSuperblockRaw::WIDTH;
+
+  /// Maximum number of file systems that can share a journal.
+  pub const MAX_USERS: usize = 48;
+
+  pub fn new<R>(inner: &mut R) -> Result<Self, Error>
+  where
+    R: io::Read,
+  {
+    let mut block: [u8; Self::RAW_WIDTH] = [0; Self::RAW_WIDTH];
+    inner.read_exact(&mut block)?;
+    let raw = JournalSuperblockRaw::from(&block);
+    let header: JournalHeader = JournalHeader::from_raw(raw.s_header);
+    header.check_signature()?;
+    // Only the v2 superblock has the fields past the error value.
+    let v2: bool = match header.block_type {
+      BlockType::SuperblockV1 => false,
+      BlockType::SuperblockV2 => true,
+      block_type => return Err(Error::BlockType(block_type)),
+    };
+    let users: Vec<Uuid> = raw
+      .s_users
+      .chunks_exact(16)
+      .take((raw.s_nr_users as usize).min(Self::MAX_USERS))
+      .map(|user| {
+        let mut uuid: [u8; 16] = [0; 16];
+        uuid.copy_from_slice(user);
+        Uuid::from(unsafe { std::mem::transmute::<[u8; 16], UuidRaw>(uuid) })
+      })
+      .collect();
+    Ok(Self {
+      header,
+      block_size: raw.s_blocksize,
+      max_len: raw.s_maxlen,
+      first: raw.s_first,
+      sequence: raw.s_sequence,
+      start: raw.s_start,
+      errno: raw.s_errno,
+      feature_compat: JournalFeatureCompat::from_raw(if v2 { raw.s_feature_compat } else { 0 }),
+      feature_incompat: JournalFeatureIncompat::from_raw(if v2 { raw.s_feature_incompat } else { 0 }),
+      feature_ro_compat: if v2 { raw.s_feature_ro_compat } else { 0 },
+      uuid: Uuid::from(raw.s_uuid),
+      nr_users: raw.s_nr_users,
+      max_transaction: raw.s_max_transaction,
+      max_trans_data: raw.s_max_trans_data,
+      checksum_type: raw.s_checksum_type,
+      num_fc_blks: raw.s_num_fc_blks,
+      head: raw.s_head,
+      checksum: raw.s_checksum,
+      users,
+    })
+  }
+
+  /// Size of a block tag in a descriptor block, not counting the UUID that may follow it.
+  pub fn get_tag_size(&self) -> usize
+  {
+    if self.feature_csum_v3() {
+      return 16;
+    }
+    let size: usize = if self.feature_csum_v2() { 14 } else { 12 };
+    if self.feature_64bit() {
+      size
+    } else {
+      size - 4
+    }
+  }
+
+  /// Size of the checksum tail at the end of descriptor and revoke blocks.
+  pub fn get_tail_size(&self) -> usize
+  {
+    if self.feature_csum_v2() || self.feature_csum_v3() {
+      4
+    } else {
+      0
+    }
+  }
+}
+
+#[derive(Debug)]
+pub enum Error
+{
+  IO(io::Error),
+  Signature(u32),
+  BlockType(BlockType),
+  BlockSize(u32, u32),
+  Unmapped(u32),
+  NoJournal,
+}
+
+impl From<io::Error> for Error
+{
+  fn from(error: io::Error) -> Self
+  {
+    Self::IO(error)
+  }
+}
+
+impl std::fmt::Display for Error
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    write!(
+      f,
+      "Journal error: {}",
+      match self {
+        Self::IO(error) => format!("An IO error occurred: {}", error),
+        Self::Signature(magic) => format!(
+          "Expected magic number was {:#010X} but found {:#010X}.",
+          JournalHeader::MAGIC_SIGNATURE,
+          magic
+        ),
+        Self::BlockType(block_type) => format!("Expected a superblock but found: {}.", block_type),
+        Self::BlockSize(journal, fs) => format!(
+          "Journal block size {} does not match the filesystem block size {}.",
+          journal, fs
+        ),
+        Self::Unmapped(block) => format!("Journal block {} is not mapped.", block),
+        Self::NoJournal => String::from("The filesystem has no internal journal."),
+      }
+    )
+  }
+}
@@ -0,0 +1,15 @@
+mod block_tag;
+mod features;
+mod journal;
+mod journal_superblock;
+mod raw;
+mod recovery;
+
+pub use block_tag::{BlockTag, TagFlags};
+pub use features::{JournalFeatureCompat, JournalFeatureIncompat};
+pub use journal::{Journal, LoggedBlock, Transaction};
+pub use journal_superblock::{BlockType, Error, JournalHeader, JournalSuperblock};
+pub(crate) use raw::{
+  CommitHeaderRaw, JournalBlockTag3Raw, JournalBlockTagRaw, JournalHeaderRaw, JournalSuperblockRaw, RevokeHeaderRaw,
+};
+pub use recovery::InodeCopy;
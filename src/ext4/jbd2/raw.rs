@@ -0,0 +1,307 @@
+use crate::uuid::UuidRaw;
+
+// Unlike the rest of the filesystem, the journal is stored big-endian.
+
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct JournalHeaderRaw
+{
+  /// jbd2 magic number, 0xC03B3998.
+  pub(crate) h_magic: u32, // 0 - 4
+  /// Description of what this block contains.
+  pub(crate) h_blocktype: u32, // 4 - 8
+  /// The transaction ID that goes with this block.
+  pub(crate) h_sequence: u32, // 8 - 12
+}
+
+impl JournalHeaderRaw
+{
+  pub(crate) const WIDTH: usize = 12;
+}
+
+impl From<&[u8; Self::WIDTH]> for JournalHeaderRaw
+{
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.h_magic = u32::from_be(raw.h_magic);
+    raw.h_blocktype = u32::from_be(raw.h_blocktype);
+    raw.h_sequence = u32::from_be(raw.h_sequence);
+    raw
+  }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct JournalSuperblockRaw
+{
+  /// Common header identifying this as a superblock.
+  pub(crate) s_header: JournalHeaderRaw, // 0 - 12
+  /// Journal device block size.
+  pub(crate) s_blocksize: u32, // 12 - 16
+  /// Total number of blocks in this journal.
+  pub(crate) s_maxlen: u32, // 16 - 20
+  /// First block of log information.
+  pub(crate) s_first: u32, // 20 - 24
+  /// First commit ID expected in log.
+  pub(crate) s_sequence: u32, // 24 - 28
+  /// Block number of the start of log. Contrary to the comments, this field
+  /// being zero does not imply that the journal is clean!
+  pub(crate) s_start: u32, // 28 - 32
+  /// Error value, as set by jbd2_journal_abort().
+  pub(crate) s_errno: i32, // 32 - 36
+  /// Compatible feature set.
+  pub(crate) s_feature_compat: u32, // 36 - 40
+  /// Incompatible feature set.
+  pub(crate) s_feature_incompat: u32, // 40 - 44
+  /// Read-only compatible feature set. There aren't any of these currently.
+  pub(crate) s_feature_ro_compat: u32, // 44 - 48
+  /// 128-bit uuid for journal. This is compared against the copy in the ext4
+  /// super block at mount time.
+  pub(crate) s_uuid: UuidRaw, // 48 - 64
+  /// Number of file systems sharing this journal.
+  pub(crate) s_nr_users: u32, // 64 - 68
+  /// Location of dynamic super block copy. (Not used?)
+  pub(crate) s_dynsuper: u32, // 68 - 72
+  /// Limit of journal blocks per transaction. (Not used?)
+  pub(crate) s_max_transaction: u32, // 72 - 76
+  /// Limit of data blocks per transaction. (Not used?)
+  pub(crate) s_max_trans_data: u32, // 76 - 80
+  /// Checksum algorithm used for the journal. 4 is crc32c.
+  pub(crate) s_checksum_type: u8, // 80 - 81
+  pub(crate) s_padding2: [u8; 3], // 81 - 84
+  /// Number of fast commit blocks in the journal.
+  pub(crate) s_num_fc_blks: u32, // 84 - 88
+  /// Block number of the head (first unused block) of the journal, only
+  /// up-to-date when the journal is empty.
+  pub(crate) s_head: u32, // 88 - 92
+  pub(crate) s_padding: [u32; 40], // 92 - 252
+  /// Checksum of the entire superblock, with this field set to zero.
+  pub(crate) s_checksum: u32, // 252 - 256
+  /// ids of all file systems sharing the log. e2fsprogs/Linux don't allow
+  /// shared external journals, but I imagine Lustre (or ocfs2?), which use
+  /// the jbd2 code, might.
+  pub(crate) s_users: [u8; 768], // 256 - 1024
+}
+
+impl JournalSuperblockRaw
+{
+  pub(crate) const WIDTH: usize = 1024;
+}
+
+impl From<&[u8; Self::WIDTH]> for JournalSuperblockRaw
+{
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.s_header.h_magic = u32::from_be(raw.s_header.h_magic);
+    raw.s_header.h_blocktype = u32::from_be(raw.s_header.h_blocktype);
+    raw.s_header.h_sequence = u32::from_be(raw.s_header.h_sequence);
+    raw.s_blocksize = u32::from_be(raw.s_blocksize);
+    raw.s_maxlen = u32::from_be(raw.s_maxlen);
+    raw.s_first = u32::from_be(raw.s_first);
+    raw.s_sequence = u32::from_be(raw.s_sequence);
+    raw.s_start = u32::from_be(raw.s_start);
+    raw.s_errno = i32::from_be(raw.s_errno);
+    raw.s_feature_compat = u32::from_be(raw.s_feature_compat);
+    raw.s_feature_incompat = u32::from_be(raw.s_feature_incompat);
+    raw.s_feature_ro_compat = u32::from_be(raw.s_feature_ro_compat);
+    raw.s_nr_users = u32::from_be(raw.s_nr_users);
+    raw.s_dynsuper = u32::from_be(raw.s_dynsuper);
+    raw.s_max_transaction = u32::from_be(raw.s_max_transaction);
+    raw.s_max_trans_data = u32::from_be(raw.s_max_trans_data);
+    raw.s_num_fc_blks = u32::from_be(raw.s_num_fc_blks);
+    raw.s_head = u32::from_be(raw.s_head);
+    raw.s_checksum = u32::from_be(raw.s_checksum);
+    raw
+  }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct JournalBlockTagRaw
+{
+  /// Lower 32-bits of the location of where the corresponding data block
+  /// should end up on disk.
+  pub(crate) t_blocknr: u32, // 0 - 4
+  /// Checksum of the journal UUID, the sequence number, and the data block.
+  /// Note that only the lower 16 bits are stored.
+  pub(crate) t_checksum: u16, // 4 - 6
+  /// Flags that go with the descriptor.
+  pub(crate) t_flags: u16, // 6 - 8
+  /// Upper 32-bits of the location of where the corresponding data block
+  /// should end up on disk. This is zero if JBD2_FEATURE_INCOMPAT_64BIT is
+  /// not enabled.
+  pub(crate) t_blocknr_high: u32, // 8 - 12
+}
+
+impl JournalBlockTagRaw
+{
+  pub(crate) const WIDTH: usize = 12;
+}
+
+impl From<&[u8; Self::WIDTH]> for JournalBlockTagRaw
+{
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.t_blocknr = u32::from_be(raw.t_blocknr);
+    raw.t_checksum = u16::from_be(raw.t_checksum);
+    raw.t_flags = u16::from_be(raw.t_flags);
+    raw.t_blocknr_high = u32::from_be(raw.t_blocknr_high);
+    raw
+  }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct JournalBlockTag3Raw
+{
+  /// Lower 32-bits of the location of where the corresponding data block
+  /// should end up on disk.
+  pub(crate) t_blocknr: u32, // 0 - 4
+  /// Flags that go with the descriptor.
+  pub(crate) t_flags: u32, // 4 - 8
+  /// Upper 32-bits of the location of where the corresponding data block
+  /// should end up on disk. This is zero if JBD2_FEATURE_INCOMPAT_64BIT is
+  /// not enabled.
+  pub(crate) t_blocknr_high: u32, // 8 - 12
+  /// Checksum of the journal UUID, the sequence number, and the data block.
+  pub(crate) t_checksum: u32, // 12 - 16
+}
+
+impl JournalBlockTag3Raw
+{
+  pub(crate) const WIDTH: usize = 16;
+}
+
+impl From<&[u8; Self::WIDTH]> for JournalBlockTag3Raw
+{
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.t_blocknr = u32::from_be(raw.t_blocknr);
+    raw.t_flags = u32::from_be(raw.t_flags);
+    raw.t_blocknr_high = u32::from_be(raw.t_blocknr_high);
+    raw.t_checksum = u32::from_be(raw.t_checksum);
+    raw
+  }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct CommitHeaderRaw
+{
+  /// Common block header.
+  pub(crate) h_header: JournalHeaderRaw, // 0 - 12
+  /// The type of checksum to use to verify the integrity of the data blocks
+  /// in the transaction.
+  pub(crate) h_chksum_type: u8, // 12 - 13
+  /// The number of bytes used by the checksum. Most likely 4.
+  pub(crate) h_chksum_size: u8, // 13 - 14
+  pub(crate) h_padding: [u8; 2], // 14 - 16
+  /// 32 bytes of space to store checksums. If JBD2_FEATURE_INCOMPAT_CSUM_V2
+  /// or JBD2_FEATURE_INCOMPAT_CSUM_V3 are set, the first __be32 is the
+  /// checksum of the journal UUID and the entire commit block, with this
+  /// field zeroed.
+  pub(crate) h_chksum: [u32; 8], // 16 - 48
+  /// The time that the transaction was committed, in seconds since the
+  /// epoch.
+  pub(crate) h_commit_sec: u64, // 48 - 56
+  /// Nanoseconds component of the above timestamp.
+  pub(crate) h_commit_nsec: u32, // 56 - 60
+  pub(crate) h_padding2: u32,    // 60 - 64
+}
+
+impl CommitHeaderRaw
+{
+  pub(crate) const WIDTH: usize = 64;
+}
+
+impl From<&[u8; Self::WIDTH]> for CommitHeaderRaw
+{
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.h_header.h_magic = u32::from_be(raw.h_header.h_magic);
+    raw.h_header.h_blocktype = u32::from_be(raw.h_header.h_blocktype);
+    raw.h_header.h_sequence = u32::from_be(raw.h_header.h_sequence);
+    raw
+      .h_chksum
+      .iter_mut()
+      .for_each(|chksum| *chksum = u32::from_be(*chksum));
+    raw.h_commit_sec = u64::from_be(raw.h_commit_sec);
+    raw.h_commit_nsec = u32::from_be(raw.h_commit_nsec);
+    raw
+  }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct RevokeHeaderRaw
+{
+  /// Common block header.
+  pub(crate) r_header: JournalHeaderRaw, // 0 - 12
+  /// Number of bytes used in this block.
+  pub(crate) r_count: u32, // 12 - 16
+}
+
+impl RevokeHeaderRaw
+{
+  pub(crate) const WIDTH: usize = 16;
+}
+
+impl From<&[u8; Self::WIDTH]> for RevokeHeaderRaw
+{
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.r_header.h_magic = u32::from_be(raw.r_header.h_magic);
+    raw.r_header.h_blocktype = u32::from_be(raw.r_header.h_blocktype);
+    raw.r_header.h_sequence = u32::from_be(raw.r_header.h_sequence);
+    raw.r_count = u32::from_be(raw.r_count);
+    raw
+  }
+}
@@ -0,0 +1,110 @@
+use super::{Error, Journal, Transaction};
+use crate::ext4::{file_sys, Extent, FileSystem, Inode};
+use chrono::{DateTime, Utc};
+use std::io;
+
+/// Copy of an inode found in an inode table block logged by the journal.
+#[derive(Debug, Clone)]
+pub struct InodeCopy
+{
+  /// Sequence number of the transaction that logged the copy.
+  pub sequence: u32,
+  /// Commit time of that transaction.
+  pub commit_time: Option<DateTime<Utc>>,
+  pub inode: Inode,
+}
+
+impl InodeCopy
+{
+  /// Whether the copy is of a file that was still in use at the time.
+  pub fn is_live(&self) -> bool
+  {
+    self.inode.dtime.timestamp() == 0 && self.inode.links_count != 0 && self.inode.mode.bits() != 0
+  }
+}
+
+impl<R> FileSystem<R>
+where
+  R: io::Read + io::Seek,
+{
+  /// Finds the copies of an inode kept in the inode table blocks that the given transactions
+  /// logged, newest first.
+  pub fn find_inode_copies(
+    &mut self,
+    journal: &Journal,
+    transactions: &[Transaction],
+    ino: u32,
+  ) -> Result<Vec<InodeCopy>, file_sys::Error>
+  {
+    let block_size: u64 = self.sb.get_block_size() as u64;
+    let inode_size: usize = self.sb.get_inode_size() as usize;
+    let offset: u64 = self.get_inode_offset(ino)?;
+    let (block, start): (u64, usize) = (offset / block_size, (offset % block_size) as usize);
+
+    let mut copies: Vec<InodeCopy> = Vec::new();
+    for transaction in transactions.iter().rev() {
+      for logged in transaction
+        .blocks
+        .iter()
+        .rev()
+        .filter(|logged| logged.tag.block == block)
+      {
+        let data: Vec<u8> = match self.read_logged_block(journal, logged) {
+          Ok(data) => data,
+          Err(file_sys::Error::Journal(Error::Unmapped(_))) => continue,
+          Err(err) => return Err(err),
+        };
+        copies.push(InodeCopy {
+          sequence: transaction.sequence,
+          commit_time: transaction.commit_time,
+          inode: self.parse_inode(ino, &data[start..start + inode_size])?,
+        });
+      }
+    }
+    Ok(copies)
+  }
+
+  /// Looks for the newest copy of a deleted inode from before it was deleted and returns it along
+  /// with the extents it maps. Only committed transactions are trusted, and copies with another
+  /// generation number than the deleted inode belonged to an earlier file and are passed over.
+  ///
+  /// Extent tree and indirect blocks are taken from the journal as well when it has a copy from no
+  /// later than the inode's, since deletion clears them too. Otherwise they are read from the disk.
+  pub fn recover_inode_from_journal(
+    &mut self,
+    journal: &Journal,
+    transactions: &[Transaction],
+    ino: u32,
+    deleted: &Inode,
+  ) -> Result<Option<(InodeCopy, Vec<Extent>)>, file_sys::Error>
+  {
+    let copy: InodeCopy = match self
+      .find_inode_copies(journal, transactions, ino)?
+      .into_iter()
+      .find(|copy| copy.commit_time.is_some() && copy.is_live() && copy.inode.generation == deleted.generation)
+    {
+      Some(copy) => copy,
+      None => return Ok(None),
+    };
+
+    let sequence: u32 = copy.sequence;
+    let mut read = |fs: &mut Self, block: u64| -> Result<Option<Vec<u8>>, file_sys::Error> {
+      let logged = transactions
+        .iter()
+        .rev()
+        .filter(|transaction| transaction.commit_time.is_some() && transaction.sequence <= sequence)
+        .find_map(|transaction| transaction.blocks.iter().rev().find(|logged| logged.tag.block == block));
+      match logged {
+        Some(logged) => fs.read_logged_block(journal, logged).map(Some),
+        None => fs.read_block(block).map(Some),
+      }
+    };
+    let blocks_count: u64 = self.sb.get_blocks_count();
+    let extents: Vec<Extent> = self
+      .read_extents_with(&copy.inode, &mut read)?
+      .into_iter()
+      .filter(|extent| extent.start != 0 && extent.start + extent.len as u64 <= blocks_count)
+      .collect();
+    Ok(Some((copy, extents)))
+  }
+}
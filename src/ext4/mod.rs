@@ -1,8 +1,17 @@
+mod block_map;
+pub mod carve;
+mod checksum;
+pub mod dir;
+pub mod extent;
 pub mod file_sys;
 pub mod group_desc;
 pub mod inode;
+pub mod jbd2;
 pub mod superblock;
+pub mod xattr;
 
+pub use checksum::ChecksumError;
+pub use extent::Extent;
 pub use file_sys::FileSystem;
 pub use group_desc::GroupDesc;
 pub use inode::Inode;
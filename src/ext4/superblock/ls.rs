@@ -6,8 +6,7 @@ impl std::fmt::Display for Superblock
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
   {
     let inode_blocks_per_group: u64 =
-      ((self.inodes_per_group as u64 * self.get_inode_size() as u64) + self.get_block_size() as u64 - 1)
-        / self.get_block_size() as u64;
+      (self.inodes_per_group as u64 * self.get_inode_size() as u64).div_ceil(self.get_block_size() as u64);
 
     writeln!(
       f,
@@ -238,7 +237,14 @@ impl std::fmt::Display for Superblock
 
     if self.feature_metadata_csum() {
       writeln!(f, "Checksum type:            {}", self.checksum_type)?;
-      writeln!(f, "Checksum:                 {:#010X}", self.checksum)?;
+      match self.check_checksum() {
+        None => writeln!(f, "Checksum:                 {:#010X} (valid)", self.checksum)?,
+        Some(_) => writeln!(
+          f,
+          "Checksum:                 {:#010X} (invalid, computed {:#010X})",
+          self.checksum, self.calculated_checksum
+        )?,
+      }
     }
 
     if !self.encrypt_pw_salt.is_null() {
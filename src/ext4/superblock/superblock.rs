@@ -2,7 +2,11 @@ use super::{
   CharEncoding, ChecksumType, Creator, DefaultMountOptions, EncryptionMode, ErrorPolicy, FeatureCompat,
   FeatureIncompat, Flags, HashVersion, ReadOnlyFeatureCompat, RevisionLevel, State, SuperblockRaw,
 };
-use crate::{ext4::inode::Inode, uuid::Uuid};
+use crate::{
+  crc,
+  ext4::{inode::Inode, ChecksumError, GroupDesc},
+  uuid::Uuid,
+};
 use chrono::{DateTime, Duration, TimeZone, Utc};
 use std::convert::{TryFrom, TryInto};
 use std::io;
@@ -229,6 +233,8 @@ pub struct Superblock
   pub encoding_flags: u16, // 638 - 640
   /// Superblock checksum.
   pub checksum: u32, // 1020 - 1024
+  /// Checksum computed over the superblock as it was read, to compare with `checksum`.
+  pub calculated_checksum: u32,
 }
 
 impl Superblock
@@ -236,6 +242,10 @@ impl Superblock
   pub const RAW_WIDTH: usize = SuperblockRaw::WIDTH;
 
   pub const MAGIC_SIGNATURE: u16 = 0xEF53;
+  /// Offset of `s_magic` within the superblock.
+  pub const MAGIC_OFFSET: usize = 56;
+  /// Offset of `s_checksum`, which the checksum covers everything in front of.
+  pub const CHECKSUM_OFFSET: usize = 1020;
 
   /// 1024
   pub const MIN_BLOCK_LOG_SIZE: u32 = 10;
@@ -248,7 +258,9 @@ impl Superblock
   {
     let mut block: [u8; Self::RAW_WIDTH] = [0; Self::RAW_WIDTH];
     inner.read_exact(&mut block)?;
-    Ok(SuperblockRaw::from(&block).try_into()?)
+    let mut sb: Self = SuperblockRaw::from(&block).try_into()?;
+    sb.calculated_checksum = crc::crc32c(!0, &block[..Self::CHECKSUM_OFFSET]);
+    Ok(sb)
   }
 
   pub fn check_signature(&self) -> Option<SignatureError>
@@ -260,6 +272,42 @@ impl Superblock
     }
   }
 
+  /// Returns the size of a group descriptor. Only 64bit filesystems record it, and their
+  /// descriptors are at least 64 bytes long.
+  pub fn get_desc_size(&self) -> u32
+  {
+    if self.feature_64bit() {
+      (self.desc_size as u32).max(GroupDesc::RAW_WIDTH64 as u32)
+    } else {
+      GroupDesc::RAW_WIDTH32 as u32
+    }
+  }
+
+  /// Returns the seed of the metadata_csum checksums, which is either stored or derived from the
+  /// UUID.
+  pub fn get_checksum_seed(&self) -> u32
+  {
+    if self.feature_csum_seed() {
+      self.checksum_seed
+    } else {
+      crc::crc32c(!0, &self.uuid.to_bytes())
+    }
+  }
+
+  /// Compares the checksum with the one computed when the superblock was read. Only superblocks
+  /// with the metadata_csum feature and the crc32c checksum type carry one.
+  pub fn check_checksum(&self) -> Option<ChecksumError>
+  {
+    if !self.feature_metadata_csum()
+      || !matches!(self.checksum_type, ChecksumType::Crc32c)
+      || self.checksum == self.calculated_checksum
+    {
+      None
+    } else {
+      Some(ChecksumError::Mismatch(self.checksum, self.calculated_checksum))
+    }
+  }
+
   pub fn get_block_size(&self) -> u32
   {
     2u32.pow(10 + self.log_block_size)
@@ -284,6 +332,15 @@ impl Superblock
     }
   }
 
+  pub fn get_first_ino(&self) -> u32
+  {
+    if self.rev_level == RevisionLevel::GOOD_OLD_REV {
+      Inode::GOOD_OLD_FIRST_INO
+    } else {
+      self.first_ino
+    }
+  }
+
   pub fn get_blocks_count(&self) -> u64
   {
     self.blocks_count_lo as u64
@@ -314,6 +371,87 @@ impl Superblock
       }
   }
 
+  /// Returns the number of block groups, the last of which may be cut short.
+  pub fn get_group_count(&self) -> u32
+  {
+    if self.blocks_per_group == 0 {
+      return 0;
+    }
+    (self
+      .get_blocks_count()
+      .saturating_sub(self.first_data_block as u64)
+      .div_ceil(self.blocks_per_group as u64)) as u32
+  }
+
+  /// Whether the given block group starts with a copy of the superblock. Without sparse_super every
+  /// group has one; with it only groups 0, 1 and the powers of 3, 5 and 7 do; and with
+  /// sparse_super2 only group 0 and the (up to) two groups in `backup_bgs`.
+  pub fn has_backup(&self, group: u32) -> bool
+  {
+    if group == 0 {
+      true
+    } else if self.feature_sparse_super2() {
+      self.backup_bgs.contains(&group)
+    } else if self.feature_sparse_super() {
+      Self::is_sparse_group(group)
+    } else {
+      true
+    }
+  }
+
+  /// Returns the number of blocks at the start of the given group taken by its copy of the
+  /// superblock and of the group descriptor table, along with the blocks reserved for the table to
+  /// grow. With meta_bg, the descriptor blocks past `first_meta_bg` are instead kept one at a time
+  /// by the first, second and last groups of their meta group.
+  pub fn get_backup_blocks(&self, group: u32) -> u64
+  {
+    let per_block: u64 = (self.get_block_size() / self.get_desc_size()).max(1) as u64;
+    let has_super: u64 = self.has_backup(group) as u64;
+    if !self.feature_meta_bg() || (group as u64) < self.first_meta_bg as u64 * per_block {
+      if has_super == 0 {
+        return 0;
+      }
+      let gdt_blocks: u64 = if self.feature_meta_bg() {
+        self.first_meta_bg as u64
+      } else {
+        (self.get_group_count() as u64).div_ceil(per_block)
+      };
+      has_super + gdt_blocks + self.reserved_gdt_blocks as u64
+    } else {
+      let first: u64 = group as u64 / per_block * per_block;
+      let group: u64 = group as u64;
+      has_super + (group == first || group == first + 1 || group == first + per_block - 1) as u64
+    }
+  }
+
+  /// Returns the block groups that hold a copy of the superblock, starting with the primary one in
+  /// group 0.
+  pub fn get_backup_groups(&self) -> Vec<u32>
+  {
+    (0..self.get_group_count())
+      .filter(|&group| self.has_backup(group))
+      .collect()
+  }
+
+  /// Whether a group is 0, 1 or a power of 3, 5 or 7, which are the groups that keep a copy of the
+  /// superblock under sparse_super.
+  pub fn is_sparse_group(group: u32) -> bool
+  {
+    if group <= 1 {
+      return true;
+    }
+    [3, 5, 7].iter().any(|&base| {
+      let mut power: u32 = base;
+      while power < group {
+        power = match power.checked_mul(base) {
+          Some(power) => power,
+          None => return false,
+        };
+      }
+      power == group
+    })
+  }
+
   pub fn get_features(&self) -> Vec<&str>
   {
     let mut features = self.feature_compat.features_list();
@@ -433,6 +571,7 @@ impl TryFrom<SuperblockRaw> for Superblock
       encoding: CharEncoding::from(raw.s_encoding),
       encoding_flags: raw.s_encoding_flags,
       checksum: raw.s_checksum,
+      calculated_checksum: 0,
     })
   }
 }
@@ -0,0 +1,178 @@
+use super::{Error, Xattr};
+use bitflags::bitflags;
+
+bitflags! {
+  pub struct AclPerm: u16
+  {
+    const EXECUTE = 0x1;
+    const WRITE = 0x2;
+    const READ = 0x4;
+  }
+}
+
+impl AclPerm
+{
+  pub fn from_raw(raw: u16) -> Self
+  {
+    unsafe { Self::from_bits_unchecked(raw) }
+  }
+}
+
+impl std::fmt::Display for AclPerm
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    write!(
+      f,
+      "{}{}{}",
+      if self.contains(Self::READ) { 'r' } else { '-' },
+      if self.contains(Self::WRITE) { 'w' } else { '-' },
+      if self.contains(Self::EXECUTE) { 'x' } else { '-' }
+    )
+  }
+}
+
+/// Whom an ACL entry applies to. Named users and groups carry their id.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AclTag
+{
+  UserObj,
+  User(u32),
+  GroupObj,
+  Group(u32),
+  Mask,
+  Other,
+  Unknown(u16),
+}
+
+impl AclTag
+{
+  const USER_OBJ: u16 = 0x1;
+  const USER: u16 = 0x2;
+  const GROUP_OBJ: u16 = 0x4;
+  const GROUP: u16 = 0x8;
+  const MASK: u16 = 0x10;
+  const OTHER: u16 = 0x20;
+}
+
+impl std::fmt::Display for AclTag
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    match self {
+      Self::UserObj => write!(f, "user:"),
+      Self::User(id) => write!(f, "user:{}", id),
+      Self::GroupObj => write!(f, "group:"),
+      Self::Group(id) => write!(f, "group:{}", id),
+      Self::Mask => write!(f, "mask:"),
+      Self::Other => write!(f, "other:"),
+      Self::Unknown(tag) => write!(f, "unknown({:#x}):", tag),
+    }
+  }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct AclEntry
+{
+  /// Whom the entry applies to.
+  pub tag: AclTag,
+  /// Permissions granted.
+  pub perm: AclPerm,
+}
+
+impl std::fmt::Display for AclEntry
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    write!(f, "{}:{}", self.tag, self.perm)
+  }
+}
+
+/// POSIX access control list, as stored in the "system.posix_acl_access" and
+/// "system.posix_acl_default" extended attributes. On disk it is a version number followed by
+/// entries that only carry an id for named users and groups.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Acl
+{
+  pub entries: Vec<AclEntry>,
+}
+
+impl Acl
+{
+  pub const VERSION: u32 = 0x0001;
+
+  /// Name of the extended attribute holding the access ACL.
+  pub const ACCESS_NAME: &'static str = "system.posix_acl_access";
+  /// Name of the extended attribute holding the default ACL of a directory.
+  pub const DEFAULT_NAME: &'static str = "system.posix_acl_default";
+
+  pub fn new(value: &[u8]) -> Result<Self, Error>
+  {
+    let version: u32 = match value.get(..4) {
+      Some(v) => u32::from_le_bytes([v[0], v[1], v[2], v[3]]),
+      None => return Err(Error::AclEntry(0)),
+    };
+    if version != Self::VERSION {
+      return Err(Error::AclVersion(version));
+    }
+
+    let mut entries: Vec<AclEntry> = Vec::new();
+    let mut offset: usize = 4;
+    while offset < value.len() {
+      let short: &[u8] = value.get(offset..offset + 4).ok_or(Error::AclEntry(offset as u32))?;
+      let tag: u16 = u16::from_le_bytes([short[0], short[1]]);
+      let perm: AclPerm = AclPerm::from_raw(u16::from_le_bytes([short[2], short[3]]));
+      // Short entries stop at the permissions, long ones go on with an id.
+      let tag: AclTag = match tag {
+        AclTag::USER | AclTag::GROUP => {
+          let id: &[u8] = value
+            .get(offset + 4..offset + 8)
+            .ok_or(Error::AclEntry(offset as u32))?;
+          offset += 4;
+          let id: u32 = u32::from_le_bytes([id[0], id[1], id[2], id[3]]);
+          if tag == AclTag::USER {
+            AclTag::User(id)
+          } else {
+            AclTag::Group(id)
+          }
+        }
+        AclTag::USER_OBJ => AclTag::UserObj,
+        AclTag::GROUP_OBJ => AclTag::GroupObj,
+        AclTag::MASK => AclTag::Mask,
+        AclTag::OTHER => AclTag::Other,
+        _ => AclTag::Unknown(tag),
+      };
+      offset += 4;
+      entries.push(AclEntry { tag, perm });
+    }
+    Ok(Self { entries })
+  }
+}
+
+impl std::fmt::Display for Acl
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    for (i, entry) in self.entries.iter().enumerate() {
+      if i != 0 {
+        write!(f, ",")?;
+      }
+      write!(f, "{}", entry)?;
+    }
+    Ok(())
+  }
+}
+
+impl Xattr
+{
+  /// Decodes the value as an ACL if this is one of the POSIX ACL attributes.
+  pub fn get_acl(&self) -> Option<Result<Acl, Error>>
+  {
+    let name: String = self.get_name();
+    if name == Acl::ACCESS_NAME || name == Acl::DEFAULT_NAME {
+      Some(Acl::new(&self.value))
+    } else {
+      None
+    }
+  }
+}
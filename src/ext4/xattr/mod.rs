@@ -0,0 +1,13 @@
+mod acl;
+mod name_index;
+mod raw;
+mod xattr;
+mod xattr_entry;
+mod xattr_header;
+
+pub use acl::{Acl, AclEntry, AclPerm, AclTag};
+pub use name_index::NameIndex;
+pub(crate) use raw::{XattrEntryRaw, XattrHeaderRaw};
+pub use xattr::Xattr;
+pub use xattr_entry::{Error, XattrEntry};
+pub use xattr_header::XattrHeader;
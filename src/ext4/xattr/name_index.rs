@@ -0,0 +1,76 @@
+/// Attribute name index, which stands for the prefix of an attribute's name. Entries only store
+/// what follows the prefix.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum NameIndex
+{
+  User,
+  PosixAclAccess,
+  PosixAclDefault,
+  Trusted,
+  Lustre,
+  Security,
+  System,
+  RichAcl,
+  Unknown(u8),
+}
+
+impl NameIndex
+{
+  const USER: u8 = 1;
+  const POSIX_ACL_ACCESS: u8 = 2;
+  const POSIX_ACL_DEFAULT: u8 = 3;
+  const TRUSTED: u8 = 4;
+  const LUSTRE: u8 = 5;
+  const SECURITY: u8 = 6;
+  const SYSTEM: u8 = 7;
+  const RICHACL: u8 = 8;
+
+  pub fn from_raw(index: u8) -> Self
+  {
+    match index {
+      Self::USER => Self::User,
+      Self::POSIX_ACL_ACCESS => Self::PosixAclAccess,
+      Self::POSIX_ACL_DEFAULT => Self::PosixAclDefault,
+      Self::TRUSTED => Self::Trusted,
+      Self::LUSTRE => Self::Lustre,
+      Self::SECURITY => Self::Security,
+      Self::SYSTEM => Self::System,
+      Self::RICHACL => Self::RichAcl,
+      _ => Self::Unknown(index),
+    }
+  }
+
+  /// The prefix this index stands for. The ACL indexes cover a whole name, so the entries using
+  /// them have an empty name.
+  pub fn prefix(&self) -> &'static str
+  {
+    match self {
+      Self::User => "user.",
+      Self::PosixAclAccess => "system.posix_acl_access",
+      Self::PosixAclDefault => "system.posix_acl_default",
+      Self::Trusted => "trusted.",
+      Self::Lustre => "lustre.",
+      Self::Security => "security.",
+      Self::System => "system.",
+      Self::RichAcl => "system.richacl",
+      Self::Unknown(_) => "",
+    }
+  }
+
+  /// Puts the prefix in front of a name stored under this index.
+  pub fn get_full_name(&self, name: &[u8]) -> String
+  {
+    format!("{}{}", self.prefix(), String::from_utf8_lossy(name))
+  }
+}
+
+impl std::fmt::Display for NameIndex
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    match self {
+      Self::Unknown(index) => write!(f, "Unknown name index {}", index),
+      _ => write!(f, "{}", self.prefix()),
+    }
+  }
+}
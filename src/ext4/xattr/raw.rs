@@ -0,0 +1,90 @@
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct XattrEntryRaw
+{
+  /// Length of name.
+  pub(crate) e_name_len: u8, // 0 - 1
+  /// Attribute name index.
+  pub(crate) e_name_index: u8, // 1 - 2
+  /// Location of this attribute's value on the disk block where it is stored.
+  /// For a block this value is relative to the start of the block (i.e. the
+  /// header). For an inode this value is relative to the start of the first
+  /// entry.
+  pub(crate) e_value_offs: u16, // 2 - 4
+  /// The inode where the value is stored. Zero indicates the value is in the
+  /// same block as this entry.
+  pub(crate) e_value_inum: u32, // 4 - 8
+  /// Length of attribute value.
+  pub(crate) e_value_size: u32, // 8 - 12
+  /// Hash value of attribute name and attribute value.
+  pub(crate) e_hash: u32, // 12 - 16
+}
+
+impl XattrEntryRaw
+{
+  pub(crate) const WIDTH: usize = 16;
+}
+
+impl From<&[u8; Self::WIDTH]> for XattrEntryRaw
+{
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.e_value_offs = u16::from_le(raw.e_value_offs);
+    raw.e_value_inum = u32::from_le(raw.e_value_inum);
+    raw.e_value_size = u32::from_le(raw.e_value_size);
+    raw.e_hash = u32::from_le(raw.e_hash);
+    raw
+  }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct XattrHeaderRaw
+{
+  /// Magic number for identification, 0xEA020000.
+  pub(crate) h_magic: u32, // 0 - 4
+  /// Reference count.
+  pub(crate) h_refcount: u32, // 4 - 8
+  /// Number of disk blocks used.
+  pub(crate) h_blocks: u32, // 8 - 12
+  /// Hash value of all attributes.
+  pub(crate) h_hash: u32, // 12 - 16
+  /// Checksum of the extended attribute block.
+  pub(crate) h_checksum: u32, // 16 - 20
+  /// Zero.
+  pub(crate) h_reserved: [u32; 3], // 20 - 32
+}
+
+impl XattrHeaderRaw
+{
+  pub(crate) const WIDTH: usize = 32;
+}
+
+impl From<&[u8; Self::WIDTH]> for XattrHeaderRaw
+{
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.h_magic = u32::from_le(raw.h_magic);
+    raw.h_refcount = u32::from_le(raw.h_refcount);
+    raw.h_blocks = u32::from_le(raw.h_blocks);
+    raw.h_hash = u32::from_le(raw.h_hash);
+    raw.h_checksum = u32::from_le(raw.h_checksum);
+    raw
+  }
+}
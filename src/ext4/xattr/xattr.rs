@@ -0,0 +1,88 @@
+use super::{NameIndex, XattrEntry, XattrHeader};
+use crate::ext4::{file_sys::Error, inode::Flags, FileSystem, Inode};
+use std::io::{self, Read};
+
+/// Extended attribute along with its value.
+#[derive(Debug, Clone)]
+pub struct Xattr
+{
+  /// Attribute name index, which stands for the name's prefix.
+  pub name_index: NameIndex,
+  /// Attribute name, without its prefix.
+  pub name: Vec<u8>,
+  /// Attribute value.
+  pub value: Vec<u8>,
+}
+
+impl Xattr
+{
+  /// The name including its prefix.
+  pub fn get_name(&self) -> String
+  {
+    self.name_index.get_full_name(&self.name)
+  }
+}
+
+impl<R> FileSystem<R>
+where
+  R: io::Read + io::Seek,
+{
+  /// Reads the extended attributes of an inode, first the ones stored in the inode itself and then
+  /// the ones in the block `file_acl` points to. Values kept in an inode of their own are read
+  /// from it.
+  pub fn read_xattrs(&mut self, inode: &Inode) -> Result<Vec<Xattr>, Error>
+  {
+    let mut xattrs: Vec<Xattr> = Vec::new();
+    for entry in inode.get_inode_xattrs()? {
+      // Values in the inode are counted from the first entry, right after the magic number.
+      let value: Vec<u8> = self.read_xattr_value(&entry, &inode.xattr_space[4..])?;
+      xattrs.push(Xattr {
+        name_index: entry.name_index,
+        name: entry.name,
+        value,
+      });
+    }
+    if inode.file_acl != 0 {
+      let block: Vec<u8> = self.read_block(inode.file_acl)?;
+      XattrHeader::new(&mut &block[..])?.check_signature()?;
+      for entry in XattrEntry::read_list(&block[XattrHeader::RAW_WIDTH..])? {
+        let value: Vec<u8> = self.read_xattr_value(&entry, &block)?;
+        xattrs.push(Xattr {
+          name_index: entry.name_index,
+          name: entry.name,
+          value,
+        });
+      }
+    }
+    Ok(xattrs)
+  }
+
+  /// Returns the value of the extended attribute with the given name, prefix included.
+  pub fn get_xattr(&mut self, inode: &Inode, name: &str) -> Result<Option<Vec<u8>>, Error>
+  {
+    Ok(
+      self
+        .read_xattrs(inode)?
+        .into_iter()
+        .find(|xattr| xattr.get_name() == name)
+        .map(|xattr| xattr.value),
+    )
+  }
+
+  fn read_xattr_value(&mut self, entry: &XattrEntry, values: &[u8]) -> Result<Vec<u8>, Error>
+  {
+    if entry.value_inum == 0 {
+      return Ok(entry.get_value(values)?.to_vec());
+    }
+    let inode: Inode = self.read_inode(entry.value_inum)?;
+    if !inode.flags.contains(Flags::EA_INODE) {
+      return Err(super::Error::EaInode(entry.value_inum).into());
+    }
+    let mut value: Vec<u8> = Vec::with_capacity(entry.value_size as usize);
+    self
+      .open_inode(inode)?
+      .take(entry.value_size as u64)
+      .read_to_end(&mut value)?;
+    Ok(value)
+  }
+}
@@ -0,0 +1,133 @@
+use super::{NameIndex, XattrEntryRaw};
+use std::io;
+
+/// Extended attribute entry. The value is stored elsewhere, either further down the same block or
+/// in an inode of its own.
+#[derive(Debug, Clone)]
+pub struct XattrEntry
+{
+  /// Attribute name index, which stands for the name's prefix.
+  pub name_index: NameIndex,
+  /// Attribute name, without its prefix.
+  pub name: Vec<u8>,
+  /// Location of this attribute's value on the disk block where it is stored.
+  /// For a block this value is relative to the start of the block (i.e. the
+  /// header). For an inode this value is relative to the start of the first
+  /// entry.
+  pub value_offs: u16,
+  /// The inode where the value is stored. Zero indicates the value is in the
+  /// same block as this entry.
+  pub value_inum: u32,
+  /// Length of attribute value.
+  pub value_size: u32,
+  /// Hash value of attribute name and attribute value.
+  pub hash: u32,
+}
+
+impl XattrEntry
+{
+  pub const RAW_WIDTH: usize = XattrEntryRaw::WIDTH;
+
+  /// Magic number at the start of the in-inode extended attribute space and of attribute blocks.
+  pub const MAGIC_SIGNATURE: u32 = 0xEA020000;
+
+  /// Name of the attribute holding the part of inline data that does not fit in `i_block`.
+  pub const INLINE_DATA_NAME: &'static [u8] = b"data";
+
+  pub fn new<R>(inner: &mut R) -> Result<Self, Error>
+  where
+    R: io::Read,
+  {
+    let mut block: [u8; Self::RAW_WIDTH] = [0; Self::RAW_WIDTH];
+    inner.read_exact(&mut block)?;
+    let raw = XattrEntryRaw::from(&block);
+    let mut name: Vec<u8> = vec![0; raw.e_name_len as usize];
+    inner.read_exact(&mut name)?;
+    Ok(Self {
+      name_index: NameIndex::from_raw(raw.e_name_index),
+      name,
+      value_offs: raw.e_value_offs,
+      value_inum: raw.e_value_inum,
+      value_size: raw.e_value_size,
+      hash: raw.e_hash,
+    })
+  }
+
+  /// Reads the entries at the start of `entries` up to the four zero bytes that end the list.
+  /// Each entry is padded to four bytes.
+  pub(crate) fn read_list(entries: &[u8]) -> Result<Vec<Self>, Error>
+  {
+    let mut list: Vec<Self> = Vec::new();
+    let mut offset: usize = 0;
+    while entries.get(offset..offset + 4).is_some_and(|end| end != [0; 4]) {
+      let entry: Self = Self::new(&mut &entries[offset..]).map_err(|_| Error::Entry(offset as u32))?;
+      offset += (Self::RAW_WIDTH + entry.name.len()).next_multiple_of(4);
+      list.push(entry);
+    }
+    Ok(list)
+  }
+
+  /// The name including its prefix.
+  pub fn get_name(&self) -> String
+  {
+    self.name_index.get_full_name(&self.name)
+  }
+
+  /// Returns the value out of `values`, which starts where `value_offs` is counted from.
+  pub fn get_value<'a>(&self, values: &'a [u8]) -> Result<&'a [u8], Error>
+  {
+    if self.value_inum != 0 {
+      return Err(Error::ValueInode(self.value_inum));
+    }
+    let start: usize = self.value_offs as usize;
+    values
+      .get(start..start + self.value_size as usize)
+      .ok_or(Error::Value(self.value_offs, self.value_size))
+  }
+}
+
+#[derive(Debug)]
+pub enum Error
+{
+  IO(io::Error),
+  Signature(u32),
+  Entry(u32),
+  Value(u16, u32),
+  ValueInode(u32),
+  EaInode(u32),
+  AclVersion(u32),
+  AclEntry(u32),
+}
+
+impl From<io::Error> for Error
+{
+  fn from(error: io::Error) -> Self
+  {
+    Self::IO(error)
+  }
+}
+
+impl std::fmt::Display for Error
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    write!(
+      f,
+      "Extended attribute error: {}",
+      match self {
+        Self::IO(error) => format!("An IO error occurred: {}", error),
+        Self::Signature(magic) => format!(
+          "Expected magic number was {:#010X} but found {:#010X}.",
+          XattrEntry::MAGIC_SIGNATURE,
+          magic
+        ),
+        Self::Entry(offset) => format!("Entry at offset {} runs past the end of its block.", offset),
+        Self::Value(offset, size) => format!("Value at offset {} with size {} is out of bounds.", offset, size),
+        Self::ValueInode(ino) => format!("Value is stored in inode {}.", ino),
+        Self::EaInode(ino) => format!("Inode {} is not an extended attribute inode.", ino),
+        Self::AclVersion(version) => format!("ACL has an unsupported version of {}.", version),
+        Self::AclEntry(offset) => format!("ACL entry at offset {} is truncated.", offset),
+      }
+    )
+  }
+}
@@ -0,0 +1,49 @@
+use super::{Error, XattrEntry, XattrHeaderRaw};
+use std::io;
+
+/// Header of an extended attribute block, which is followed by the entries. The values are packed
+/// from the end of the block.
+#[derive(Debug)]
+pub struct XattrHeader
+{
+  /// Magic number for identification, 0xEA020000.
+  pub magic: u32,
+  /// Reference count.
+  pub refcount: u32,
+  /// Number of disk blocks used.
+  pub blocks: u32,
+  /// Hash value of all attributes.
+  pub hash: u32,
+  /// Checksum of the extended attribute block.
+  pub checksum: u32,
+}
+
+impl XattrHeader
+{
+  pub const RAW_WIDTH: usize = XattrHeaderRaw::WIDTH;
+
+  pub fn new<R>(inner: &mut R) -> Result<Self, Error>
+  where
+    R: io::Read,
+  {
+    let mut block: [u8; Self::RAW_WIDTH] = [0; Self::RAW_WIDTH];
+    inner.read_exact(&mut block)?;
+    let raw = XattrHeaderRaw::from(&block);
+    Ok(Self {
+      magic: raw.h_magic,
+      refcount: raw.h_refcount,
+      blocks: raw.h_blocks,
+      hash: raw.h_hash,
+      checksum: raw.h_checksum,
+    })
+  }
+
+  pub fn check_signature(&self) -> Result<(), Error>
+  {
+    if self.magic == XattrEntry::MAGIC_SIGNATURE {
+      Ok(())
+    } else {
+      Err(Error::Signature(self.magic))
+    }
+  }
+}
@@ -1,3 +1,7 @@
+#![allow(clippy::module_inception)]
+
+pub(crate) mod crc;
 pub mod ext4;
+pub mod partition;
 pub(crate) mod util;
 pub mod uuid;
@@ -0,0 +1,117 @@
+use super::{Error, GptEntryRaw, GptHeaderRaw, Partition, PartitionType, Scheme};
+use crate::{crc, uuid::Uuid};
+use std::io::{self, SeekFrom};
+
+/// Signature of a GPT header.
+const SIGNATURE: &[u8; 8] = b"EFI PART";
+/// Sector sizes the GPT is looked for with. Nothing records the one in use.
+const SECTOR_SIZES: [u64; 2] = [512, 4096];
+/// Smallest valid header size.
+const HEADER_SIZE: u32 = 92;
+/// Offset of the header checksum, which is zeroed while the checksum is computed.
+const HEADER_CRC32_OFFSET: usize = 16;
+/// Largest partition entry array that is read, which is far beyond what any tool creates.
+const MAX_ENTRIES_SIZE: u64 = 1 << 24;
+
+/// Reads the GPT that a protective MBR points to. The header and the partition entries both carry
+/// a CRC32; if either does not match, the backup header in the last sector of the disk and the
+/// entries it points to are used instead.
+pub(crate) fn read_partitions<R>(inner: &mut R) -> Result<(Scheme, Vec<Partition>), Error>
+where
+  R: io::Read + io::Seek,
+{
+  let mut primary_error: Option<Error> = None;
+  for &sector_size in SECTOR_SIZES.iter() {
+    match read_table(inner, 1, sector_size) {
+      Ok((disk_guid, partitions)) => {
+        return Ok((
+          Scheme::Gpt {
+            disk_guid,
+            from_backup: false,
+          },
+          partitions,
+        ))
+      }
+      Err(Error::GptSignature) => {}
+      Err(err) => {
+        primary_error.get_or_insert(err);
+      }
+    }
+  }
+
+  let len: u64 = inner.seek(SeekFrom::End(0))?;
+  for &sector_size in SECTOR_SIZES.iter() {
+    if len / sector_size < 2 {
+      continue;
+    }
+    if let Ok((disk_guid, partitions)) = read_table(inner, len / sector_size - 1, sector_size) {
+      return Ok((
+        Scheme::Gpt {
+          disk_guid,
+          from_backup: true,
+        },
+        partitions,
+      ));
+    }
+  }
+  Err(primary_error.unwrap_or(Error::GptSignature))
+}
+
+/// Reads and checks the GPT header in the given sector and the partition entries it points to.
+fn read_table<R>(inner: &mut R, lba: u64, sector_size: u64) -> Result<(Uuid, Vec<Partition>), Error>
+where
+  R: io::Read + io::Seek,
+{
+  let mut sector: Vec<u8> = vec![0; sector_size as usize];
+  inner.seek(SeekFrom::Start(lba * sector_size))?;
+  inner.read_exact(&mut sector)?;
+  let mut block: [u8; GptHeaderRaw::WIDTH] = [0; GptHeaderRaw::WIDTH];
+  block.copy_from_slice(&sector[..GptHeaderRaw::WIDTH]);
+  let header = GptHeaderRaw::from(&block);
+  if &header.signature != SIGNATURE {
+    return Err(Error::GptSignature);
+  }
+  if header.header_size < HEADER_SIZE || header.header_size as u64 > sector_size {
+    return Err(Error::GptHeaderSize(header.header_size));
+  }
+  sector[HEADER_CRC32_OFFSET..HEADER_CRC32_OFFSET + 4].fill(0);
+  let checksum: u32 = crc::crc32(&sector[..header.header_size as usize]);
+  if checksum != header.header_crc32 {
+    return Err(Error::GptHeaderChecksum(header.header_crc32, checksum));
+  }
+
+  let entries_size: u64 = header.num_entries as u64 * header.entry_size as u64;
+  if (header.entry_size as usize) < GptEntryRaw::WIDTH
+    || !header.entry_size.is_power_of_two()
+    || entries_size > MAX_ENTRIES_SIZE
+  {
+    return Err(Error::GptEntrySize(header.entry_size));
+  }
+  let mut entries: Vec<u8> = vec![0; entries_size as usize];
+  inner.seek(SeekFrom::Start(header.entries_lba * sector_size))?;
+  inner.read_exact(&mut entries)?;
+  let checksum: u32 = crc::crc32(&entries);
+  if checksum != header.entries_crc32 {
+    return Err(Error::GptEntriesChecksum(header.entries_crc32, checksum));
+  }
+
+  let mut partitions: Vec<Partition> = Vec::new();
+  for (i, entry) in entries.chunks_exact(header.entry_size as usize).enumerate() {
+    let mut block: [u8; GptEntryRaw::WIDTH] = [0; GptEntryRaw::WIDTH];
+    block.copy_from_slice(&entry[..GptEntryRaw::WIDTH]);
+    let entry = GptEntryRaw::from(&block);
+    if entry.type_guid == [0; 16] {
+      continue;
+    }
+    let name_len: usize = entry.name.iter().position(|&c| c == 0).unwrap_or(entry.name.len());
+    partitions.push(Partition {
+      number: i as u32 + 1,
+      offset: entry.first_lba * sector_size,
+      size: (entry.last_lba + 1).saturating_sub(entry.first_lba) * sector_size,
+      partition_type: PartitionType::Gpt(Uuid::from_guid(entry.type_guid)),
+      name: String::from_utf16_lossy(&entry.name[..name_len]),
+      ext: false,
+    });
+  }
+  Ok((Uuid::from_guid(header.disk_guid), partitions))
+}
@@ -0,0 +1,96 @@
+use super::{Error, MbrEntryRaw, Partition, PartitionTable, PartitionType};
+use std::collections::HashSet;
+use std::io::{self, SeekFrom};
+
+/// Boot signature that ends the MBR and every EBR.
+pub(crate) const SIGNATURE: u16 = 0xAA55;
+/// Offset of the boot signature in the MBR and EBRs.
+pub(crate) const SIGNATURE_OFFSET: usize = 510;
+/// Type of the single partition of a protective MBR, which covers the GPT disk.
+pub(crate) const GPT_PROTECTIVE: u8 = 0xEE;
+/// Offset of the partition entries in the MBR and EBRs.
+const ENTRIES_OFFSET: usize = 446;
+/// Number of partition entries in the MBR.
+const ENTRIES: usize = 4;
+/// Number of the first logical partition.
+const FIRST_LOGICAL: u32 = 5;
+
+pub(crate) fn read_entries(sector: &[u8; PartitionTable::SECTOR_SIZE as usize]) -> Vec<MbrEntryRaw>
+{
+  (0..ENTRIES)
+    .map(|i| {
+      let mut block: [u8; MbrEntryRaw::WIDTH] = [0; MbrEntryRaw::WIDTH];
+      let start: usize = ENTRIES_OFFSET + i * MbrEntryRaw::WIDTH;
+      block.copy_from_slice(&sector[start..start + MbrEntryRaw::WIDTH]);
+      MbrEntryRaw::from(&block)
+    })
+    .collect()
+}
+
+/// Lists the primary partitions of the MBR, followed by the logical partitions of the extended
+/// ones.
+pub(crate) fn read_partitions<R>(
+  inner: &mut R,
+  sector: &[u8; PartitionTable::SECTOR_SIZE as usize],
+) -> Result<Vec<Partition>, Error>
+where
+  R: io::Read + io::Seek,
+{
+  let mut partitions: Vec<Partition> = Vec::new();
+  let mut logical: Vec<Partition> = Vec::new();
+  for (i, entry) in read_entries(sector).into_iter().enumerate() {
+    if entry.os_type == 0 || entry.sectors == 0 {
+      continue;
+    }
+    let partition = new_partition(i as u32 + 1, entry.lba_first as u64, &entry);
+    if partition.partition_type.is_extended() {
+      read_logical_partitions(inner, entry.lba_first as u64, &mut logical)?;
+    }
+    partitions.push(partition);
+  }
+  partitions.append(&mut logical);
+  Ok(partitions)
+}
+
+/// Follows the chain of EBRs of an extended partition. Each EBR describes a logical partition
+/// relative to itself, and links to the next EBR relative to the start of the extended partition.
+/// A damaged link ends the chain.
+fn read_logical_partitions<R>(inner: &mut R, start: u64, partitions: &mut Vec<Partition>) -> Result<(), Error>
+where
+  R: io::Read + io::Seek,
+{
+  let mut ebr: u64 = start;
+  let mut seen: HashSet<u64> = HashSet::new();
+  while seen.insert(ebr) {
+    let mut sector: [u8; PartitionTable::SECTOR_SIZE as usize] = [0; PartitionTable::SECTOR_SIZE as usize];
+    inner.seek(SeekFrom::Start(ebr * PartitionTable::SECTOR_SIZE))?;
+    if inner.read_exact(&mut sector).is_err()
+      || u16::from_le_bytes([sector[SIGNATURE_OFFSET], sector[SIGNATURE_OFFSET + 1]]) != SIGNATURE
+    {
+      break;
+    }
+
+    let entries: Vec<MbrEntryRaw> = read_entries(&sector);
+    if entries[0].os_type != 0 && entries[0].sectors != 0 {
+      let number: u32 = FIRST_LOGICAL + partitions.len() as u32;
+      partitions.push(new_partition(number, ebr + entries[0].lba_first as u64, &entries[0]));
+    }
+    if !PartitionType::Mbr(entries[1].os_type).is_extended() || entries[1].lba_first == 0 {
+      break;
+    }
+    ebr = start + entries[1].lba_first as u64;
+  }
+  Ok(())
+}
+
+fn new_partition(number: u32, lba: u64, entry: &MbrEntryRaw) -> Partition
+{
+  Partition {
+    number,
+    offset: lba * PartitionTable::SECTOR_SIZE,
+    size: entry.sectors as u64 * PartitionTable::SECTOR_SIZE,
+    partition_type: PartitionType::Mbr(entry.os_type),
+    name: String::new(),
+    ext: false,
+  }
+}
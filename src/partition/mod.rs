@@ -0,0 +1,7 @@
+mod gpt;
+mod mbr;
+mod partition;
+mod raw;
+
+pub use partition::{Error, Partition, PartitionTable, PartitionType, Scheme};
+pub(crate) use raw::{GptEntryRaw, GptHeaderRaw, MbrEntryRaw};
@@ -0,0 +1,217 @@
+use super::{gpt, mbr};
+use crate::{
+  ext4::{FileSystem, Superblock},
+  uuid::Uuid,
+};
+use std::io::{self, SeekFrom};
+
+/// Type of a partition, as recorded by the table it comes from.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PartitionType
+{
+  /// MBR system ID.
+  Mbr(u8),
+  /// GPT partition type GUID.
+  Gpt(Uuid),
+}
+
+impl PartitionType
+{
+  const GPT_TYPES: [(&'static str, &'static str); 8] = [
+    ("C12A7328-F81F-11D2-BA4B-00A0C93EC93B", "EFI System"),
+    ("21686148-6449-6E6F-744E-656564454649", "BIOS boot"),
+    ("0FC63DAF-8483-4772-8E79-3D69D8477DE4", "Linux filesystem"),
+    ("0657FD6D-A4AB-43C4-84E5-0933C84B4F4F", "Linux swap"),
+    ("E6D6D379-F507-44C2-A23C-238F2A3DF928", "Linux LVM"),
+    ("A19D880F-05FC-4D3B-A006-743F0F84911E", "Linux RAID"),
+    ("933AC7E1-2EB4-4F13-B844-0E14E2AEF915", "Linux home"),
+    ("EBD0A0A2-B9E5-4433-87C0-68B6B72699C7", "Microsoft basic data"),
+  ];
+
+  /// Whether this is an MBR extended partition, which holds the logical partitions.
+  pub fn is_extended(&self) -> bool
+  {
+    matches!(self, Self::Mbr(0x05) | Self::Mbr(0x0F) | Self::Mbr(0x85))
+  }
+
+  /// Returns a short description of the well-known types.
+  pub fn description(&self) -> &str
+  {
+    match self {
+      Self::Mbr(0x00) => "Empty",
+      Self::Mbr(0x05) | Self::Mbr(0x0F) | Self::Mbr(0x85) => "Extended",
+      Self::Mbr(0x07) => "NTFS/exFAT",
+      Self::Mbr(0x0B) | Self::Mbr(0x0C) => "FAT32",
+      Self::Mbr(0x82) => "Linux swap",
+      Self::Mbr(0x83) => "Linux",
+      Self::Mbr(0x8E) => "Linux LVM",
+      Self::Mbr(0xEE) => "GPT protective",
+      Self::Mbr(0xEF) => "EFI System",
+      Self::Mbr(0xFD) => "Linux RAID",
+      Self::Mbr(_) => "Unknown",
+      Self::Gpt(guid) => {
+        let guid: String = guid.to_string();
+        Self::GPT_TYPES
+          .iter()
+          .find(|(known, _)| *known == guid)
+          .map_or("Unknown", |(_, description)| description)
+      }
+    }
+  }
+}
+
+impl std::fmt::Display for PartitionType
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    match self {
+      Self::Mbr(id) => write!(f, "{:#04X} ({})", id, self.description()),
+      Self::Gpt(guid) => write!(f, "{} ({})", guid, self.description()),
+    }
+  }
+}
+
+/// Partition of a disk image.
+#[derive(Debug)]
+pub struct Partition
+{
+  /// Number of the partition, starting at 1. Logical MBR partitions start at 5.
+  pub number: u32,
+  /// Byte offset of the partition from the start of the image.
+  pub offset: u64,
+  /// Size of the partition in bytes.
+  pub size: u64,
+  pub partition_type: PartitionType,
+  /// Name of the partition. Only GPT partitions have one.
+  pub name: String,
+  /// Whether an ext2/3/4 superblock magic number sits where the superblock of the partition goes.
+  pub ext: bool,
+}
+
+/// Partitioning scheme of a disk image.
+#[derive(Debug)]
+pub enum Scheme
+{
+  Mbr,
+  Gpt
+  {
+    /// GUID of the disk.
+    disk_guid: Uuid,
+    /// Whether the primary header or its entries were damaged, so the backup ones were used.
+    from_backup: bool,
+  },
+}
+
+/// Partition table of a disk image.
+#[derive(Debug)]
+pub struct PartitionTable
+{
+  pub scheme: Scheme,
+  pub partitions: Vec<Partition>,
+}
+
+impl PartitionTable
+{
+  /// Size of the sectors the MBR addresses in, and the GPT is looked for with first.
+  pub const SECTOR_SIZE: u64 = 512;
+
+  /// Reads the partition table at the start of a disk image. A protective MBR hands over to the
+  /// GPT.
+  pub fn new<R>(inner: &mut R) -> Result<Self, Error>
+  where
+    R: io::Read + io::Seek,
+  {
+    let mut sector: [u8; Self::SECTOR_SIZE as usize] = [0; Self::SECTOR_SIZE as usize];
+    inner.seek(SeekFrom::Start(0))?;
+    inner.read_exact(&mut sector)?;
+    let signature: u16 = u16::from_le_bytes([sector[mbr::SIGNATURE_OFFSET], sector[mbr::SIGNATURE_OFFSET + 1]]);
+    if signature != mbr::SIGNATURE {
+      return Err(Error::Signature(signature));
+    }
+
+    let (scheme, mut partitions) = if mbr::read_entries(&sector)
+      .iter()
+      .any(|entry| entry.os_type == mbr::GPT_PROTECTIVE)
+    {
+      gpt::read_partitions(inner)?
+    } else {
+      (Scheme::Mbr, mbr::read_partitions(inner, &sector)?)
+    };
+    for partition in partitions.iter_mut() {
+      partition.ext = has_ext_magic(inner, partition.offset);
+    }
+    Ok(Self { scheme, partitions })
+  }
+
+  /// Returns the partition with the given number.
+  pub fn get(&self, number: u32) -> Option<&Partition>
+  {
+    self.partitions.iter().find(|partition| partition.number == number)
+  }
+}
+
+fn has_ext_magic<R>(inner: &mut R, offset: u64) -> bool
+where
+  R: io::Read + io::Seek,
+{
+  let mut magic: [u8; 2] = [0; 2];
+  inner
+    .seek(SeekFrom::Start(
+      offset + FileSystem::<()>::START_OFFSET + Superblock::MAGIC_OFFSET as u64,
+    ))
+    .and_then(|_| inner.read_exact(&mut magic))
+    .is_ok()
+    && u16::from_le_bytes(magic) == Superblock::MAGIC_SIGNATURE
+}
+
+#[derive(Debug)]
+pub enum Error
+{
+  IO(io::Error),
+  Signature(u16),
+  GptSignature,
+  GptHeaderSize(u32),
+  GptEntrySize(u32),
+  GptHeaderChecksum(u32, u32),
+  GptEntriesChecksum(u32, u32),
+  NotFound(u32),
+}
+
+impl From<io::Error> for Error
+{
+  fn from(error: io::Error) -> Self
+  {
+    Self::IO(error)
+  }
+}
+
+impl std::fmt::Display for Error
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    write!(
+      f,
+      "Partition table error: {}",
+      match self {
+        Self::IO(error) => format!("An IO error occurred: {}", error),
+        Self::Signature(signature) => format!(
+          "Expected boot signature was {:#06X} but found {:#06X}.",
+          mbr::SIGNATURE,
+          signature
+        ),
+        Self::GptSignature => String::from("The protective MBR is not followed by a GPT header."),
+        Self::GptHeaderSize(size) => format!("Invalid GPT header size: {}", size),
+        Self::GptEntrySize(size) => format!("Invalid GPT partition entry size: {}", size),
+        Self::GptHeaderChecksum(expected, found) => format!(
+          "GPT header checksum mismatch: expected {:#010X} but found {:#010X}.",
+          expected, found
+        ),
+        Self::GptEntriesChecksum(expected, found) => format!(
+          "GPT partition entries checksum mismatch: expected {:#010X} but found {:#010X}.",
+          expected, found
+        ),
+        Self::NotFound(number) => format!("There is no partition number {}.", number),
+      }
+    )
+  }
+}
@@ -0,0 +1,152 @@
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct MbrEntryRaw
+{
+  /// 0x80 if the partition is bootable, 0x00 otherwise.
+  pub(crate) status: u8, // 0 - 1
+  /// CHS address of the first sector.
+  pub(crate) chs_first: [u8; 3], // 1 - 4
+  /// Partition type.
+  pub(crate) os_type: u8, // 4 - 5
+  /// CHS address of the last sector.
+  pub(crate) chs_last: [u8; 3], // 5 - 8
+  /// LBA of the first sector, relative to the table the entry is in for logical partitions.
+  pub(crate) lba_first: u32, // 8 - 12
+  /// Number of sectors.
+  pub(crate) sectors: u32, // 12 - 16
+}
+
+impl MbrEntryRaw
+{
+  pub(crate) const WIDTH: usize = 16;
+}
+
+impl From<&[u8; Self::WIDTH]> for MbrEntryRaw
+{
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.lba_first = u32::from_le(raw.lba_first);
+    raw.sectors = u32::from_le(raw.sectors);
+    raw
+  }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct GptHeaderRaw
+{
+  /// "EFI PART".
+  pub(crate) signature: [u8; 8], // 0 - 8
+  /// Revision, 0x00010000 for 1.0.
+  pub(crate) revision: u32, // 8 - 12
+  /// Size of the header in bytes, 92 for revision 1.0.
+  pub(crate) header_size: u32, // 12 - 16
+  /// CRC32 of the header, computed with this field zeroed.
+  pub(crate) header_crc32: u32, // 16 - 20
+  pub(crate) reserved: u32, // 20 - 24
+  /// LBA of this header.
+  pub(crate) current_lba: u64, // 24 - 32
+  /// LBA of the other header.
+  pub(crate) backup_lba: u64, // 32 - 40
+  /// First LBA usable by partitions.
+  pub(crate) first_usable_lba: u64, // 40 - 48
+  /// Last LBA usable by partitions.
+  pub(crate) last_usable_lba: u64, // 48 - 56
+  /// GUID of the disk.
+  pub(crate) disk_guid: [u8; 16], // 56 - 72
+  /// LBA of the partition entry array.
+  pub(crate) entries_lba: u64, // 72 - 80
+  /// Number of partition entries.
+  pub(crate) num_entries: u32, // 80 - 84
+  /// Size of a partition entry, 128 or a larger power of two.
+  pub(crate) entry_size: u32, // 84 - 88
+  /// CRC32 of the partition entry array.
+  pub(crate) entries_crc32: u32, // 88 - 92
+  /// Start of the reserved space that fills the rest of the sector.
+  pub(crate) padding: u32, // 92 - 96
+}
+
+impl GptHeaderRaw
+{
+  pub(crate) const WIDTH: usize = 96;
+}
+
+impl From<&[u8; Self::WIDTH]> for GptHeaderRaw
+{
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.revision = u32::from_le(raw.revision);
+    raw.header_size = u32::from_le(raw.header_size);
+    raw.header_crc32 = u32::from_le(raw.header_crc32);
+    raw.current_lba = u64::from_le(raw.current_lba);
+    raw.backup_lba = u64::from_le(raw.backup_lba);
+    raw.first_usable_lba = u64::from_le(raw.first_usable_lba);
+    raw.last_usable_lba = u64::from_le(raw.last_usable_lba);
+    raw.entries_lba = u64::from_le(raw.entries_lba);
+    raw.num_entries = u32::from_le(raw.num_entries);
+    raw.entry_size = u32::from_le(raw.entry_size);
+    raw.entries_crc32 = u32::from_le(raw.entries_crc32);
+    raw
+  }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub(crate) struct GptEntryRaw
+{
+  /// Partition type GUID, zero for unused entries.
+  pub(crate) type_guid: [u8; 16], // 0 - 16
+  /// GUID unique to the partition.
+  pub(crate) unique_guid: [u8; 16], // 16 - 32
+  /// First LBA.
+  pub(crate) first_lba: u64, // 32 - 40
+  /// Last LBA, inclusive.
+  pub(crate) last_lba: u64, // 40 - 48
+  /// Attribute flags.
+  pub(crate) attributes: u64, // 48 - 56
+  /// Partition name in UTF-16LE.
+  pub(crate) name: [u16; 36], // 56 - 128
+}
+
+impl GptEntryRaw
+{
+  pub(crate) const WIDTH: usize = 128;
+}
+
+impl From<&[u8; Self::WIDTH]> for GptEntryRaw
+{
+  #[cfg(target_endian = "little")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) }
+  }
+
+  #[cfg(target_endian = "big")]
+  fn from(block: &[u8; Self::WIDTH]) -> Self
+  {
+    let mut raw = unsafe { std::mem::transmute::<[u8; Self::WIDTH], Self>(*block) };
+    raw.first_lba = u64::from_le(raw.first_lba);
+    raw.last_lba = u64::from_le(raw.last_lba);
+    raw.attributes = u64::from_le(raw.attributes);
+    for c in raw.name.iter_mut() {
+      *c = u16::from_le(*c);
+    }
+    raw
+  }
+}
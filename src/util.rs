@@ -1,18 +1,11 @@
 use chrono::{DateTime, TimeZone, Utc};
 
-#[cfg(target_endian = "little")]
+/// Joins the lower and upper halves of a field. Both halves are expected to be in native byte order
+/// already, and `$ty` must be twice as wide as each half.
 #[macro_export]
 macro_rules! concat_lo_hi {
   ($ty:ty, $lo:expr, $hi:expr) => {
-    ($lo as $ty) | ($hi as $ty) << (std::mem::size_of::<$ty>())
-  };
-}
-
-#[cfg(target_endian = "big")]
-#[macro_export]
-macro_rules! concat_lo_hi {
-  ($ty:ty, $lo:expr, $hi:expr) => {
-    ($lo as $ty) << (std::mem::size_of::<$ty>()) | ($hi as $ty)
+    ($lo as $ty) | ($hi as $ty) << (std::mem::size_of::<$ty>() * 4)
   };
 }
 
@@ -26,9 +19,9 @@ macro_rules! add_to_list {
 }
 
 #[inline(always)]
-pub fn get_string_list<'a>(list: &'a Vec<&str>) -> String
+pub fn get_string_list(list: &[&str]) -> String
 {
-  if list.len() == 0 {
+  if list.is_empty() {
     String::from("(none)")
   } else {
     list.join(" ")
@@ -38,7 +31,7 @@ pub fn get_string_list<'a>(list: &'a Vec<&str>) -> String
 #[inline(always)]
 pub fn get_string(string: &str) -> &str
 {
-  if string.bytes().nth(0).unwrap_or(0) == 0 {
+  if string.as_bytes().first().copied().unwrap_or(0) == 0 {
     "<not available>"
   } else {
     string
@@ -23,7 +23,7 @@ impl Uuid
 {
   pub fn is_null(&self) -> bool
   {
-    if self.time_low == 0
+    self.time_low == 0
       && self.time_mid == 0
       && self.time_hi_and_version == 0
       && self.clock_seq == 0
@@ -33,12 +33,34 @@ impl Uuid
       && self.node[3] == 0
       && self.node[4] == 0
       && self.node[5] == 0
-    {
-      true
-    } else {
-      false
+  }
+
+  /// Decodes a GUID the way Microsoft and the GPT store it, with its first three fields in little
+  /// endian.
+  pub fn from_guid(bytes: [u8; 16]) -> Self
+  {
+    let mut node: [u8; 6] = [0; 6];
+    node.copy_from_slice(&bytes[10..16]);
+    Self {
+      time_low: u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+      time_mid: u16::from_le_bytes([bytes[4], bytes[5]]),
+      time_hi_and_version: u16::from_le_bytes([bytes[6], bytes[7]]),
+      clock_seq: u16::from_be_bytes([bytes[8], bytes[9]]),
+      node,
     }
   }
+
+  /// The UUID as the 16 bytes it is stored as on disk.
+  pub fn to_bytes(&self) -> [u8; 16]
+  {
+    let mut bytes: [u8; 16] = [0; 16];
+    bytes[0..4].copy_from_slice(&self.time_low.to_be_bytes());
+    bytes[4..6].copy_from_slice(&self.time_mid.to_be_bytes());
+    bytes[6..8].copy_from_slice(&self.time_hi_and_version.to_be_bytes());
+    bytes[8..10].copy_from_slice(&self.clock_seq.to_be_bytes());
+    bytes[10..16].copy_from_slice(&self.node);
+    bytes
+  }
 }
 
 impl From<UuidRaw> for Uuid